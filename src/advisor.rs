@@ -0,0 +1,157 @@
+use crate::cards;
+use crate::error::{self, RftgError};
+use crate::game;
+use crate::search;
+
+/// What one player can infer about the other players' hands.
+///
+/// Every card from the deck list that the player hasn't seen is treated as
+/// equally likely to be anywhere out of sight: in a hand, in the draw pile
+/// or face down in the discard pile. The cards the player has seen are
+/// their own hand and explored cards, every tableau, the cards they
+/// discarded face down themselves and the cards discarded face up, since the
+/// last reshuffle.
+#[derive(Debug, Clone)]
+pub struct Advisor<'a> {
+    state: &'a game::GameState,
+    unseen: Vec<cards::Card>,
+}
+
+impl<'a> Advisor<'a> {
+    /// Advise `player`, an index into `GameState::players`. After a draft
+    /// every player draws from their own deck, which the others only partly
+    /// know, so there is nothing to go on.
+    pub fn new(state: &'a game::GameState, player: usize) -> error::Result<Advisor<'a>> {
+        if state.is_drafted() {
+            return Err(RftgError::InvalidData("Hands can't be inferred after a draft.".to_string()));
+        }
+        let me = state.player(player);
+
+        // When mixing, the explored cards are in the hand too, so they are
+        // only counted once.
+        let mut seen: Vec<&cards::Card> = me.hand().iter().collect();
+        if me.is_mixing() {
+            for card in me.explored() {
+                if let Some(index) = seen.iter().position(|&c| { c == card }) {
+                    seen.remove(index);
+                }
+            }
+        }
+        seen.extend(me.explored().iter());
+        seen.extend(me.search_found());
+        seen.extend(me.discards_in_pile(state.game().reshuffles()));
+        for p in state.players().iter() {
+            seen.extend(p.tableau().iter());
+        }
+        if let Some(robot) = state.get_robot() {
+            seen.extend(robot.tableau().iter());
+        }
+        seen.extend(state.game().public_discards().iter());
+
+        let mut unseen = state.game().deck_list().to_vec();
+        for card in seen {
+            if let Some(index) = unseen.iter().position(|c| { c == card }) {
+                unseen.remove(index);
+            }
+        }
+
+        Ok(Advisor { state, unseen })
+    }
+
+    /// The cards this player hasn't seen.
+    pub fn unseen(&self) -> &[cards::Card] {
+        &self.unseen
+    }
+
+    /// The fewest cards an opponent has seen: the cards in their hand and
+    /// tableau, and the ones they have discarded.
+    pub fn seen_by(&self, opponent: usize) -> usize {
+        let p = self.state.player(opponent);
+        p.hand().len() + p.tableau().len() + p.face_down_discards()
+    }
+
+    /// The chance that an opponent holds at least one card matching `query`.
+    pub fn chance(&self, opponent: usize, query: &search::Query) -> f64 {
+        let total = self.unseen.len();
+        let matching = self.unseen.iter().filter(|card| { query.matches(card) }).count();
+        let hand = self.state.player(opponent).hand().len().min(total);
+
+        // The chance that none of the cards in their hand match, drawing
+        // them one by one from the unseen cards.
+        let mut none = 1.0;
+        for i in 0..hand {
+            if total - i <= matching {
+                return 1.0;
+            }
+            none *= (total - matching - i) as f64 / (total - i) as f64;
+        }
+        1.0 - none
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{self, test_cards};
+    use crate::game::{Action, Game, GameState, Move};
+    use crate::player;
+    use crate::search;
+    use super::Advisor;
+
+    fn choose(deck: Vec<cards::Card>, actions: [Action; 2]) -> GameState {
+        let mut state = GameState::with_game(Game::from_deck(deck, 2), 2);
+        for (player, action) in actions.into_iter().enumerate() {
+            state = state.apply(Move::ChooseActions { player, actions: vec![action] }).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn own_payments_are_seen() {
+        let state = choose(test_cards::developments(12, 2), [Action::Develop, Action::Develop]);
+        let paid = state.player(0).hand()[1].clone();
+        let placement = player::Placement { index: 0, payment: vec![1] };
+        let state = state.apply(Move::Develop { player: 0, placement: Some(placement) }).unwrap();
+
+        // Player 0 has seen the three cards they were dealt, including the
+        // one they paid with. Player 1 has seen their own hand and the card
+        // that was placed.
+        let advisor = Advisor::new(&state, 0).unwrap();
+        assert_eq!(advisor.unseen().len(), 12 - 3);
+        assert!(!advisor.unseen().contains(&paid));
+
+        let advisor = Advisor::new(&state, 1).unwrap();
+        assert_eq!(advisor.unseen().len(), 12 - 4);
+        assert!(advisor.unseen().contains(&paid));
+    }
+
+    #[test]
+    fn mixed_explored_cards_are_seen_once() {
+        let state = choose(test_cards::developments(20, 2), [Action::ExploreMix, Action::Develop]);
+        let state = state.apply(Move::Explore { player: 0 }).unwrap();
+        assert!(state.player(0).is_mixing());
+
+        // The three explored cards are in the hand, with the three dealt.
+        let advisor = Advisor::new(&state, 0).unwrap();
+        assert_eq!(advisor.unseen().len(), 20 - 6);
+        assert!(state.player(0).explored().iter().all(|card| { !advisor.unseen().contains(card) }));
+
+        let state = state.apply(Move::ExploreMixDiscard { player: 0, discard: vec![0] }).unwrap();
+        assert_eq!(Advisor::new(&state, 0).unwrap().unseen().len(), 20 - 6);
+    }
+
+    #[test]
+    fn chance_of_holding_a_matching_card() {
+        let mut deck = test_cards::developments(8, 2);
+        deck.extend((0..4).map(|i| { test_cards::world(&format!("World {}", i), 1) }));
+        let state = GameState::with_game(Game::from_deck(deck, 2), 2);
+        let advisor = Advisor::new(&state, 0).unwrap();
+        let query = search::Query::new().card_type(cards::CardType::World);
+
+        // Player 1 holds 3 of the 9 cards player 0 hasn't seen, and 4 worlds
+        // less the ones in player 0's hand are among them. With 4 of 9, the
+        // chance of none is 5/9 * 4/8 * 3/7 = 5/42.
+        let worlds = advisor.unseen().iter().filter(|card| { query.matches(card) }).count();
+        let expected = [0.0, 1.0 - 2.0 / 3.0, 1.0 - 5.0 / 12.0, 1.0 - 5.0 / 21.0, 1.0 - 5.0 / 42.0][worlds];
+        assert!((advisor.chance(1, &query) - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+/// The cards added by the Brink of War expansion, which bring prestige:
+/// some are worth prestige when placed, and some earn it for conquering
+/// military worlds. Trade, consume and produce powers are left off.
+pub fn get_cards() -> Vec<Card> {
+    let cards = vec![
+        // Worlds.
+        Card::new("Rebel Freedom Fighters")
+            .card_type(CardType::World)
+            .military_cost(4)
+            .add_points(Points::Simple(2))
+            .add_attribute(Attribute::Rebel)
+            .add_power(Power::SettleMilitaryBonus(1)),
+
+        Card::new("Psi-Crystal World")
+            .card_type(CardType::World)
+            .trade_cost(4)
+            .add_points(Points::Simple(2))
+            .produces(Production::Produces, Good::Genes)
+            .add_power(Power::Prestige(1)),
+
+        Card::new("Alien Tourist Attraction")
+            .card_type(CardType::World)
+            .military_cost(6)
+            .add_points(Points::Simple(3))
+            .add_attribute(Attribute::Alien)
+            .add_power(Power::Prestige(1)),
+
+        // Developments.
+        Card::new("Imperium Supply Convoy")
+            .card_type(CardType::Development)
+            .trade_cost(3)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryBonus(1))
+            .add_power(Power::SettlePrestigeIfMilitary(1)),
+
+        Card::new("Galactic Power Brokers")
+            .card_type(CardType::Development)
+            .trade_cost(4)
+            .add_points(Points::Simple(2))
+            .add_power(Power::Prestige(1)),
+
+        Card::new("Imperium War Faction")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(2, vec![Condition::Attribute(Attribute::Imperium)]))
+            .add_points(Points::TableauConditions(1, vec![
+                Condition::MilitaryWorld,
+                Condition::Not(Box::new(Condition::Attribute(Attribute::Imperium))),
+            ]))
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryBonus(1))
+            .add_power(Power::SettlePrestigeIfMilitary(1)),
+
+        Card::new("Universal Peace Institute")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(1, vec![
+                Condition::CardType(CardType::World),
+                Condition::Not(Box::new(Condition::MilitaryWorld)),
+            ]))
+            .add_power(Power::Prestige(1))
+            .add_power(Power::TakeoverDefense(2)),
+    ];
+
+    cards.into_iter().map(|card| { card.expansion(Expansion::BrinkOfWar) }).collect()
+}
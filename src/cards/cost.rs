@@ -0,0 +1,42 @@
+use std::ops;
+
+/// What it takes to place a card: cards from hand for a trade cost, or
+/// enough military to conquer a world's defense.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Cost {
+    #[default]
+    Free,
+    Trade(i32),
+    Military(i32),
+}
+
+impl Cost {
+    /// The trade cost, or 0 for free cards and military worlds.
+    pub fn trade(&self) -> i32 {
+        match *self {
+            Cost::Trade(n) => n,
+            _ => 0,
+        }
+    }
+
+    /// The defense of a military world, or 0 for anything else.
+    pub fn military(&self) -> i32 {
+        match *self {
+            Cost::Military(n) => n,
+            _ => 0,
+        }
+    }
+}
+
+/// Adding to a cost changes its number and keeps its kind. Free stays free.
+impl ops::Add<i32> for Cost {
+    type Output = Cost;
+
+    fn add(self, n: i32) -> Cost {
+        match self {
+            Cost::Free => Cost::Free,
+            Cost::Trade(t) => Cost::Trade(t + n),
+            Cost::Military(m) => Cost::Military(m + n),
+        }
+    }
+}
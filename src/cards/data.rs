@@ -0,0 +1,391 @@
+use super::*;
+
+/// The cards in the base set, with the powers the engine knows how to use.
+/// Trade, consume and produce powers are left off, since there are no phases
+/// for them yet.
+pub fn get_cards() -> Vec<Card> {
+    // Start worlds.
+    let mut cards = vec![
+        Card::new("Alpha Centauri")
+            .card_type(CardType::World)
+            .trade_cost(2)
+            .add_points(Points::Simple(0))
+            .produces(Production::Windfall, Good::RareElements)
+            .add_attribute(Attribute::Starter)
+            .add_power(Power::SettleDiscountIfGood(1, Some(Good::RareElements)))
+            .add_power(Power::SettleMilitaryIfGood(1, Some(Good::RareElements))),
+        Card::new("Earth's Lost Colony")
+            .card_type(CardType::World)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))
+            .produces(Production::Produces, Good::Novelty)
+            .add_attribute(Attribute::Starter),
+        Card::new("Epsilon Eridani")
+            .card_type(CardType::World)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_power(Power::SettleMilitaryBonus(1)),
+        Card::new("New Sparta")
+            .card_type(CardType::World)
+            .military_cost(2)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_power(Power::SettleMilitaryBonus(2)),
+        Card::new("Old Earth")
+            .card_type(CardType::World)
+            .trade_cost(3)
+            .add_points(Points::Simple(2))
+            .add_attribute(Attribute::Starter),
+    ];
+
+    // Military worlds.
+    cards.push(Card::new("New Survivalists")
+        .card_type(CardType::World)
+        .military_cost(1)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Novelty));
+    cards.push(Card::new("Rebel Fuel Cache")
+        .card_type(CardType::World)
+        .military_cost(1)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::RareElements)
+        .add_attribute(Attribute::Rebel));
+    cards.push(Card::new("Former Penal Colony")
+        .card_type(CardType::World)
+        .military_cost(2)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Novelty)
+        .add_power(Power::SettleMilitaryBonus(1)));
+    cards.push(Card::new("Rebel Miners")
+        .card_type(CardType::World)
+        .military_cost(2)
+        .add_points(Points::Simple(1))
+        .produces(Production::Produces, Good::RareElements)
+        .add_attribute(Attribute::Rebel));
+    cards.push(Card::new("Alien Robot Sentry")
+        .card_type(CardType::World)
+        .military_cost(2)
+        .add_points(Points::Simple(2))
+        .produces(Production::Windfall, Good::AlienTechnology)
+        .add_attribute(Attribute::Alien));
+    for name in ["Aquatic Uplift Race", "Avian Uplift Race", "Reptilian Uplift Race"] {
+        cards.push(Card::new(name)
+            .card_type(CardType::World)
+            .military_cost(2)
+            .add_points(Points::Simple(2))
+            .produces(Production::Windfall, Good::Genes)
+            .add_attribute(Attribute::Uplift));
+    }
+    cards.push(Card::new("Bio-Hazard Mining World")
+        .card_type(CardType::World)
+        .military_cost(3)
+        .add_points(Points::Simple(2))
+        .produces(Production::Produces, Good::RareElements));
+    cards.push(Card::new("Rebel Warrior Race")
+        .card_type(CardType::World)
+        .military_cost(3)
+        .add_points(Points::Simple(2))
+        .produces(Production::Windfall, Good::Genes)
+        .add_attribute(Attribute::Rebel)
+        .add_power(Power::SettleMilitaryBonus(1)));
+    cards.push(Card::new("Malevolent Lifeforms")
+        .card_type(CardType::World)
+        .military_cost(4)
+        .add_points(Points::Simple(2))
+        .produces(Production::Windfall, Good::Genes));
+    cards.push(Card::new("Rebel Underground")
+        .card_type(CardType::World)
+        .military_cost(4)
+        .add_points(Points::Simple(4))
+        .add_attribute(Attribute::Rebel));
+    cards.push(Card::new("Lost Alien Warship")
+        .card_type(CardType::World)
+        .military_cost(5)
+        .add_points(Points::Simple(3))
+        .produces(Production::Windfall, Good::AlienTechnology)
+        .add_attribute(Attribute::Alien)
+        .add_power(Power::SettleMilitaryBonus(2)));
+    cards.push(Card::new("Lost Species Ark World")
+        .card_type(CardType::World)
+        .military_cost(5)
+        .add_points(Points::Simple(3))
+        .produces(Production::Windfall, Good::Genes)
+        .add_attribute(Attribute::Uplift));
+    cards.push(Card::new("Rebel Outpost")
+        .card_type(CardType::World)
+        .military_cost(5)
+        .add_points(Points::Simple(5))
+        .add_attribute(Attribute::Rebel)
+        .add_power(Power::SettleMilitaryBonus(1)));
+    cards.push(Card::new("Rebel Base")
+        .card_type(CardType::World)
+        .military_cost(6)
+        .add_points(Points::Simple(6))
+        .add_attribute(Attribute::Rebel));
+    cards.push(Card::new("Rebel Homeworld")
+        .card_type(CardType::World)
+        .military_cost(7)
+        .add_points(Points::Simple(7))
+        .add_attribute(Attribute::Rebel));
+
+    // Non-military worlds.
+    cards.push(Card::new("Plague World")
+        .card_type(CardType::World)
+        .trade_cost(0)
+        .add_points(Points::Simple(0))
+        .produces(Production::Windfall, Good::Genes));
+    cards.push(Card::new("Refugee World")
+        .card_type(CardType::World)
+        .trade_cost(0)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Novelty));
+    cards.push(Card::new("Destroyed World")
+        .card_type(CardType::World)
+        .trade_cost(1)
+        .add_points(Points::Simple(0))
+        .produces(Production::Windfall, Good::RareElements));
+    for name in ["Expanding Colony", "Secluded World"] {
+        cards.push(Card::new(name)
+            .card_type(CardType::World)
+            .trade_cost(1)
+            .add_points(Points::Simple(1))
+            .produces(Production::Produces, Good::Novelty));
+    }
+    cards.push(Card::new("Artist Colony")
+        .card_type(CardType::World)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Novelty));
+    cards.push(Card::new("Empath World")
+        .card_type(CardType::World)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Genes));
+    for name in ["Black Market Trading World", "Gambling World", "Outlaw World"] {
+        cards.push(Card::new(name)
+            .card_type(CardType::World)
+            .trade_cost(1)
+            .add_points(Points::Simple(1)));
+    }
+    for name in ["Gem World", "New Vinland", "Space Port", "Spice World"] {
+        cards.push(Card::new(name)
+            .card_type(CardType::World)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))
+            .produces(Production::Produces, Good::Novelty));
+    }
+    cards.push(Card::new("Pre-Sentient Race")
+        .card_type(CardType::World)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .produces(Production::Windfall, Good::Genes));
+    cards.push(Card::new("Radioactive World")
+        .card_type(CardType::World)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .produces(Production::Produces, Good::RareElements));
+    cards.push(Card::new("Comet Zone")
+        .card_type(CardType::World)
+        .trade_cost(3)
+        .add_points(Points::Simple(2))
+        .produces(Production::Windfall, Good::RareElements));
+    cards.push(Card::new("Galactic Resort")
+        .card_type(CardType::World)
+        .trade_cost(3)
+        .add_points(Points::Simple(2))
+        .produces(Production::Windfall, Good::Novelty));
+    cards.push(Card::new("Mining World")
+        .card_type(CardType::World)
+        .trade_cost(4)
+        .add_points(Points::Simple(2))
+        .produces(Production::Produces, Good::RareElements));
+    cards.push(Card::new("Deserted Alien Outpost")
+        .card_type(CardType::World)
+        .trade_cost(4)
+        .add_points(Points::Simple(3))
+        .produces(Production::Windfall, Good::AlienTechnology)
+        .add_attribute(Attribute::Alien));
+    cards.push(Card::new("Tourist World")
+        .card_type(CardType::World)
+        .trade_cost(4)
+        .add_points(Points::Simple(4)));
+    cards.push(Card::new("New Earth")
+        .card_type(CardType::World)
+        .trade_cost(5)
+        .add_points(Points::Simple(3))
+        .produces(Production::Produces, Good::Genes));
+    cards.push(Card::new("Deserted Alien Colony")
+        .card_type(CardType::World)
+        .trade_cost(5)
+        .add_points(Points::Simple(4))
+        .produces(Production::Produces, Good::AlienTechnology)
+        .add_attribute(Attribute::Alien));
+    cards.push(Card::new("Deserted Alien Library")
+        .card_type(CardType::World)
+        .trade_cost(6)
+        .add_points(Points::Simple(5))
+        .produces(Production::Produces, Good::AlienTechnology)
+        .add_attribute(Attribute::Alien));
+
+    // Developments, most of which come in pairs.
+    cards.extend(copies(2, Card::new("Contact Specialist")
+        .card_type(CardType::Development)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .add_power(Power::SettleMilitaryBonus(-1))
+        .add_power(Power::SettleMilitaryAsTradeWithDiscount(1))));
+    cards.extend(copies(2, Card::new("Expedition Force")
+        .card_type(CardType::Development)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .add_power(Power::ExploreSeeBonus(1))
+        .add_power(Power::SettleMilitaryBonus(1))));
+    cards.extend(copies(2, Card::new("Investment Credits")
+        .card_type(CardType::Development)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .add_power(Power::DevelopDiscount(1))));
+    cards.extend(copies(2, Card::new("New Military Tactics")
+        .card_type(CardType::Development)
+        .trade_cost(1)
+        .add_points(Points::Simple(1))
+        .add_power(Power::SettleDiscardForMilitary(3))));
+    for name in ["Export Duties", "Public Works"] {
+        cards.extend(copies(2, Card::new(name)
+            .card_type(CardType::Development)
+            .trade_cost(1)
+            .add_points(Points::Simple(1))));
+    }
+    cards.extend(copies(2, Card::new("Colony Ship")
+        .card_type(CardType::Development)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .add_power(Power::SettleDiscardToNegateTradeIfGood(None))));
+    cards.extend(copies(2, Card::new("Mining Robots")
+        .card_type(CardType::Development)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .add_power(Power::SettleDiscountIfGood(1, Some(Good::RareElements)))
+        .add_power(Power::SettleMilitaryIfGood(1, Some(Good::RareElements)))));
+    cards.extend(copies(2, Card::new("Research Labs")
+        .card_type(CardType::Development)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .add_power(Power::ExploreSeeBonus(1))));
+    cards.extend(copies(2, Card::new("Space Marines")
+        .card_type(CardType::Development)
+        .trade_cost(2)
+        .add_points(Points::Simple(1))
+        .add_power(Power::SettleMilitaryBonus(2))));
+    for name in ["Deficit Spending", "Genetics Lab", "Interstellar Bank"] {
+        cards.extend(copies(2, Card::new(name)
+            .card_type(CardType::Development)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))));
+    }
+    for name in ["Mining Conglomerate", "Terraforming Robots"] {
+        cards.extend(copies(2, Card::new(name)
+            .card_type(CardType::Development)
+            .trade_cost(3)
+            .add_points(Points::Simple(2))));
+    }
+    cards.extend(copies(2, Card::new("Drop Ships")
+        .card_type(CardType::Development)
+        .trade_cost(4)
+        .add_points(Points::Simple(2))
+        .add_power(Power::SettleMilitaryBonus(3))));
+    cards.extend(copies(2, Card::new("Replicant Robots")
+        .card_type(CardType::Development)
+        .trade_cost(4)
+        .add_points(Points::Simple(2))
+        .add_power(Power::SettleTradeDiscount(2))));
+    cards.push(Card::new("Diversified Economy")
+        .card_type(CardType::Development)
+        .trade_cost(4)
+        .add_points(Points::Simple(2)));
+    for name in ["Consumer Markets", "Galactic Trendsetters"] {
+        cards.push(Card::new(name)
+            .card_type(CardType::Development)
+            .trade_cost(5)
+            .add_points(Points::Simple(3)));
+    }
+
+    // Six cost developments, scored by the rest of the tableau.
+    let six_cost = |name: &str| { Card::new(name).card_type(CardType::Development).trade_cost(6) };
+    let named = |n: i32, name: &str| { Points::TableauConditions(n, vec![Condition::Named(name.to_string())]) };
+    let produces = |n: i32, production: Production, good: Good| {
+        Points::TableauConditions(n, vec![Condition::Produces(production, good)])
+    };
+
+    cards.push(six_cost("Alien Tech Institute")
+        .add_points(produces(3, Production::Produces, Good::AlienTechnology))
+        .add_points(produces(2, Production::Windfall, Good::AlienTechnology))
+        .add_points(Points::TableauConditions(2, vec![
+            Condition::Attribute(Attribute::Alien),
+            Condition::Not(Box::new(Condition::Good(Good::AlienTechnology))),
+        ]))
+        .add_power(Power::SettleDiscountIfGood(2, Some(Good::AlienTechnology)))
+        .add_power(Power::SettleMilitaryIfGood(2, Some(Good::AlienTechnology))));
+    cards.push(six_cost("Free Trade Association")
+        .add_points(produces(2, Production::Produces, Good::Novelty))
+        .add_points(produces(1, Production::Windfall, Good::Novelty))
+        .add_points(named(2, "Consumer Markets"))
+        .add_points(named(2, "Expanding Colony")));
+    cards.push(six_cost("Galactic Federation")
+        .add_points(Points::TableauConditions(2, vec![
+            Condition::CardType(CardType::Development),
+            Condition::MinCost(Cost::Trade(6)),
+        ]))
+        .add_points(Points::TableauConditions(1, vec![
+            Condition::CardType(CardType::Development),
+            Condition::Not(Box::new(Condition::MinCost(Cost::Trade(6)))),
+        ]))
+        .add_power(Power::DevelopDiscount(2)));
+    cards.push(six_cost("Galactic Imperium")
+        .add_points(Points::TableauConditions(2, vec![
+            Condition::MilitaryWorld,
+            Condition::Attribute(Attribute::Rebel),
+        ]))
+        .add_points(Points::TableauConditions(1, vec![
+            Condition::MilitaryWorld,
+            Condition::Not(Box::new(Condition::Attribute(Attribute::Rebel))),
+        ]))
+        .add_attribute(Attribute::Imperium)
+        .add_power(Power::SettleMilitaryIfAttribute(4, Attribute::Rebel)));
+    cards.push(six_cost("Galactic Renaissance")
+        .add_points(Points::PerVPChip(3))
+        .add_points(named(3, "Research Labs"))
+        .add_points(named(3, "Galactic Trendsetters"))
+        .add_points(named(3, "Artist Colony"))
+        .add_power(Power::ExploreSeeBonus(1))
+        .add_power(Power::ExploreKeepBonus(1)));
+    cards.push(six_cost("Galactic Survey: SETI")
+        .add_points(Points::TableauConditions(1, vec![Condition::PowerType(PowerType::Explore)]))
+        .add_points(Points::TableauConditions(1, vec![Condition::CardType(CardType::World)]))
+        .add_power(Power::ExploreSeeBonus(2)));
+    cards.push(six_cost("Merchant Guild")
+        .add_points(produces(2, Production::Produces, Good::Novelty))
+        .add_points(produces(2, Production::Produces, Good::RareElements))
+        .add_points(produces(2, Production::Produces, Good::Genes))
+        .add_points(produces(2, Production::Produces, Good::AlienTechnology)));
+    cards.push(six_cost("Mining League")
+        .add_points(produces(2, Production::Produces, Good::RareElements))
+        .add_points(produces(1, Production::Windfall, Good::RareElements))
+        .add_points(named(2, "Mining Robots"))
+        .add_points(named(3, "Mining Conglomerate")));
+    cards.push(six_cost("New Galactic Order")
+        .add_points(Points::Military)
+        .add_power(Power::SettleMilitaryBonus(2)));
+    cards.push(six_cost("Pan-Galactic League")
+        .add_points(Points::TableauConditions(2, vec![Condition::Good(Good::Genes)]))
+        .add_points(Points::TableauConditions(1, vec![Condition::MilitaryWorld]))
+        .add_points(named(3, "Contact Specialist"))
+        .add_power(Power::SettleMilitaryBonus(-1)));
+
+    cards
+}
+
+fn copies(count: usize, card: Card) -> Vec<Card> {
+    vec![card; count]
+}
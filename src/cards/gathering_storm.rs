@@ -0,0 +1,141 @@
+use super::*;
+
+/// The cards added by the Gathering Storm expansion, including the start
+/// worlds for a fifth player. As in the base set, trade, consume and produce
+/// powers are left off.
+pub fn get_cards() -> Vec<Card> {
+    let cards = vec![
+        // Start worlds, only used with a fifth player.
+        Card::new("Doomed World")
+            .card_type(CardType::World)
+            .trade_cost(0)
+            .add_points(Points::Simple(-1))
+            .add_attribute(Attribute::Starter)
+            .add_power(Power::SettleDiscardToNegateTradeIfGood(None))
+            .min_players(5),
+
+        Card::new("Separatist Colony")
+            .card_type(CardType::World)
+            .military_cost(0)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_attribute(Attribute::Rebel)
+            .add_power(Power::SettleMilitaryBonus(1))
+            .min_players(5),
+
+        Card::new("Ancient Race")
+            .card_type(CardType::World)
+            .trade_cost(0)
+            .produces(Production::Windfall, Good::Genes)
+            .add_attribute(Attribute::Starter)
+            .min_players(5),
+
+        Card::new("Damaged Alien Factory")
+            .card_type(CardType::World)
+            .trade_cost(0)
+            .produces(Production::Produces, Good::AlienTechnology)
+            .add_attribute(Attribute::Starter)
+            .add_attribute(Attribute::Alien)
+            .min_players(5),
+
+        // Worlds.
+        Card::new("Star Nomad Lair")
+            .card_type(CardType::World)
+            .trade_cost(1)
+            .add_points(Points::Simple(1))
+            .produces(Production::Windfall, Good::Novelty),
+
+        Card::new("Black Hole Miners")
+            .card_type(CardType::World)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))
+            .produces(Production::Produces, Good::RareElements),
+
+        Card::new("Mining Mole Uplift Race")
+            .card_type(CardType::World)
+            .military_cost(2)
+            .add_points(Points::Simple(1))
+            .produces(Production::Produces, Good::RareElements)
+            .add_attribute(Attribute::Uplift),
+
+        Card::new("Blaster Gem Mines")
+            .card_type(CardType::World)
+            .military_cost(3)
+            .add_points(Points::Simple(1))
+            .produces(Production::Windfall, Good::RareElements),
+
+        Card::new("Hidden Fortress")
+            .card_type(CardType::World)
+            .military_cost(3)
+            .add_points(Points::Simple(1))
+            .add_power(Power::SettleMilitaryBonus(1))
+            .add_power(Power::TakeoverDefense(2)),
+
+        Card::new("Prosperous World")
+            .card_type(CardType::World)
+            .trade_cost(3)
+            .add_points(Points::Simple(2))
+            .produces(Production::Produces, Good::Novelty),
+
+        Card::new("Alien Rosetta Stone World")
+            .card_type(CardType::World)
+            .trade_cost(4)
+            .add_points(Points::Simple(3))
+            .add_attribute(Attribute::Alien)
+            .add_power(Power::SettleDiscountIfGood(2, Some(Good::AlienTechnology))),
+
+        Card::new("Terraformed World")
+            .card_type(CardType::World)
+            .trade_cost(5)
+            .add_points(Points::Simple(5)),
+
+        // Developments.
+        Card::new("Space Mercenaries")
+            .card_type(CardType::Development)
+            .trade_cost(1)
+            .add_points(Points::Simple(0))
+            .add_power(Power::SettleMilitaryBonus(1))
+            .add_power(Power::SettleDiscardHandForMilitary(2)),
+
+        Card::new("Clandestine Uplift Lab")
+            .card_type(CardType::Development)
+            .trade_cost(2)
+            .add_points(Points::Simple(1))
+            .add_power(Power::SettleMilitaryIfAttribute(2, Attribute::Uplift)),
+
+        Card::new("Improved Logistics")
+            .card_type(CardType::Development)
+            .trade_cost(3)
+            .add_points(Points::Simple(2)),
+
+        Card::new("Mercenary Fleet")
+            .card_type(CardType::Development)
+            .trade_cost(3)
+            .add_points(Points::Simple(2))
+            .add_power(Power::SettleMilitaryBonus(2)),
+
+        Card::new("Galactic Genome Project")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(2, vec![Condition::Good(Good::Genes)]))
+            .add_points(Points::TableauConditions(2, vec![
+                Condition::Attribute(Attribute::Uplift),
+                Condition::Not(Box::new(Condition::Good(Good::Genes))),
+            ]))
+            .add_points(Points::TableauConditions(3, vec![Condition::Named("Genetics Lab".to_string())]))
+            .add_power(Power::SettleDiscountIfGood(2, Some(Good::Genes))),
+
+        Card::new("Terraforming Guild")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(2, vec![Condition::Produces(Production::Windfall, Good::Novelty)]))
+            .add_points(Points::TableauConditions(2, vec![Condition::Produces(Production::Windfall, Good::RareElements)]))
+            .add_points(Points::TableauConditions(2, vec![Condition::Produces(Production::Windfall, Good::Genes)]))
+            .add_points(Points::TableauConditions(2, vec![
+                Condition::Produces(Production::Windfall, Good::AlienTechnology),
+            ]))
+            .add_points(Points::TableauConditions(3, vec![Condition::Named("Terraforming Robots".to_string())])),
+    ];
+
+    cards.into_iter().map(|card| { card.expansion(Expansion::GatheringStorm) }).collect()
+}
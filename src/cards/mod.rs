@@ -1,10 +1,15 @@
 mod data;
 mod cost;
+mod gathering_storm;
+mod rebel_vs_imperium;
+mod brink_of_war;
+#[cfg(test)]
+pub(crate) mod test_cards;
 
 use std::default::Default;
 use std::fmt;
 
-use utils;
+use crate::utils;
 
 pub use self::data::get_cards;
 pub use self::cost::Cost;
@@ -28,28 +33,83 @@ impl utils::Variants for Good {
     }
 }
 
+/// The box a card comes from.
+#[derive(Debug, PartialEq, Clone, Eq, Hash, Default)]
+pub enum Expansion {
+    #[default]
+    Base,
+    GatheringStorm,
+    RebelVsImperium,
+    BrinkOfWar,
+}
+
+impl utils::Variants for Expansion {
+    fn variants() -> Vec<Expansion> {
+        vec![
+            Expansion::Base,
+            Expansion::GatheringStorm,
+            Expansion::RebelVsImperium,
+            Expansion::BrinkOfWar,
+        ]
+    }
+}
+
+/// The base set plus the cards from each of `expansions`.
+pub fn get_deck(expansions: &[Expansion]) -> Vec<Card> {
+    let mut deck = get_cards();
+    if expansions.contains(&Expansion::GatheringStorm) {
+        deck.extend(gathering_storm::get_cards());
+    }
+    if expansions.contains(&Expansion::RebelVsImperium) {
+        deck.extend(rebel_vs_imperium::get_cards());
+    }
+    if expansions.contains(&Expansion::BrinkOfWar) {
+        deck.extend(brink_of_war::get_cards());
+    }
+    deck
+}
+
+/// Like `get_deck`, but without the cards that aren't used with this many
+/// players.
+pub fn get_deck_for(expansions: &[Expansion], num_players: usize) -> Vec<Card> {
+    get_deck(expansions).into_iter()
+        .filter(|card| { card.is_available_for(num_players) })
+        .collect()
+}
+
+/// The most players the base game and `expansions` have components for.
+pub fn max_players(expansions: &[Expansion]) -> usize {
+    let mut max = 4;
+    if expansions.contains(&Expansion::GatheringStorm) {
+        max += 1;
+    }
+    if expansions.contains(&Expansion::RebelVsImperium) {
+        max += 1;
+    }
+    max
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Production {
     Windfall,
     Produces,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub enum CardType {
+    #[default]
     World,
     Development,
 }
 
-impl Default for CardType {
-    fn default() -> CardType {
-        CardType::World
-    }
-}
-
 #[derive(Debug, PartialEq, Clone)]
 pub enum Power {
     ExploreSeeBonus(i32),
     ExploreKeepBonus(i32),
+    ExploreSeeBonusIfChosen(i32),
+    ExploreKeepBonusIfChosen(i32),
+    ExploreMix,
+    ExploreDiscardThenSee(i32),
 
     DevelopDiscount(i32),
     DevelopDraw(i32),
@@ -62,13 +122,115 @@ pub enum Power {
     SettleMilitaryAsTradeWithDiscount(i32),
     SettleDiscardForMilitary(i32),
     SettleDiscardToNegateTradeIfGood(Option<Good>),
+    SettleDiscardHandForMilitary(i32),
+
+    TakeoverRebel,
+    TakeoverImperium,
+    TakeoverDefense(i32),
+
+    Prestige(i32),
+    SettlePrestigeIfMilitary(i32),
+
+    HandLimitBonus(i32),
+}
+
+impl Power {
+    /// The phase a power is used in, if it is tied to one.
+    pub fn power_type(&self) -> Option<PowerType> {
+        match *self {
+            Power::ExploreSeeBonus(_) |
+            Power::ExploreKeepBonus(_) |
+            Power::ExploreSeeBonusIfChosen(_) |
+            Power::ExploreKeepBonusIfChosen(_) |
+            Power::ExploreMix |
+            Power::ExploreDiscardThenSee(_) => Some(PowerType::Explore),
+
+            Power::DevelopDiscount(_) |
+            Power::DevelopDraw(_) => Some(PowerType::Develop),
+
+            Power::SettleMilitaryBonus(_) |
+            Power::SettleTradeDiscount(_) |
+            Power::SettleDiscountIfGood(_, _) |
+            Power::SettleMilitaryIfGood(_, _) |
+            Power::SettleMilitaryIfAttribute(_, _) |
+            Power::SettleMilitaryAsTradeWithDiscount(_) |
+            Power::SettleDiscardForMilitary(_) |
+            Power::SettleDiscardToNegateTradeIfGood(_) |
+            Power::SettleDiscardHandForMilitary(_) |
+            Power::TakeoverRebel |
+            Power::TakeoverImperium |
+            Power::TakeoverDefense(_) |
+            Power::SettlePrestigeIfMilitary(_) => Some(PowerType::Settle),
+
+            Power::Prestige(_) |
+            Power::HandLimitBonus(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Power {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let good_name = |good: &Option<Good>| {
+            match *good {
+                Some(ref good) => format!("{:?}", good),
+                None => "any".to_string(),
+            }
+        };
+
+        match *self {
+            Power::ExploreSeeBonus(n) => write!(fmt, "Explore: see +{} cards.", n),
+            Power::ExploreKeepBonus(n) => write!(fmt, "Explore: keep +{} cards.", n),
+            Power::ExploreSeeBonusIfChosen(n) =>
+                write!(fmt, "Explore: see +{} cards if you chose Explore.", n),
+            Power::ExploreKeepBonusIfChosen(n) =>
+                write!(fmt, "Explore: keep +{} cards if you chose Explore.", n),
+            Power::ExploreMix =>
+                write!(fmt, "Explore: add explored cards to your hand, then discard."),
+            Power::ExploreDiscardThenSee(n) =>
+                write!(fmt, "Explore: you may discard a card from hand to see +{} cards.", n),
+
+            Power::DevelopDiscount(n) => write!(fmt, "Develop: -{} cost.", n),
+            Power::DevelopDraw(n) => write!(fmt, "Develop: draw {} cards before developing.", n),
+
+            Power::SettleMilitaryBonus(n) => write!(fmt, "Settle: +{} military.", n),
+            Power::SettleTradeDiscount(n) => write!(fmt, "Settle: -{} cost of non-military worlds.", n),
+            Power::SettleDiscountIfGood(n, ref good) =>
+                write!(fmt, "Settle: -{} cost of {} worlds.", n, good_name(good)),
+            Power::SettleMilitaryIfGood(n, ref good) =>
+                write!(fmt, "Settle: +{} military against {} worlds.", n, good_name(good)),
+            Power::SettleMilitaryIfAttribute(n, ref attr) =>
+                write!(fmt, "Settle: +{} military against {:?} worlds.", n, attr),
+            Power::SettleMilitaryAsTradeWithDiscount(n) =>
+                write!(fmt, "Settle: pay for non-alien military worlds with cards, at -{} cost.", n),
+            Power::SettleDiscardForMilitary(n) =>
+                write!(fmt, "Settle: discard this card from your tableau for +{} military.", n),
+            Power::SettleDiscardToNegateTradeIfGood(None) =>
+                write!(fmt, "Settle: discard this card from your tableau to place a non-military world for free."),
+            Power::SettleDiscardToNegateTradeIfGood(Some(ref good)) =>
+                write!(fmt, "Settle: discard this card from your tableau to place a non-military {:?} world for free.",
+                       good),
+            Power::SettleDiscardHandForMilitary(n) =>
+                write!(fmt, "Settle: discard up to {} cards from hand for +1 military each.", n),
+
+            Power::TakeoverRebel => write!(fmt, "Settle: you may take over a Rebel military world."),
+            Power::TakeoverImperium =>
+                write!(fmt, "Settle: you may take over a military world of a player with an Imperium card."),
+            Power::TakeoverDefense(n) => write!(fmt, "+{} defense against takeovers.", n),
+
+            Power::Prestige(n) => write!(fmt, "Gain {} prestige when placed.", n),
+            Power::SettlePrestigeIfMilitary(n) =>
+                write!(fmt, "Settle: gain {} prestige after conquering a military world.", n),
+
+            Power::HandLimitBonus(n) => write!(fmt, "+{} hand limit.", n),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-enum PowerType {
+pub enum PowerType {
     Explore,
-    // Develop,
-    // Settle,
+    Develop,
+    Settle,
 }
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
@@ -92,8 +254,103 @@ impl utils::Variants for Attribute {
     }
 }
 
+impl utils::Variants for CardType {
+    fn variants() -> Vec<CardType> {
+        vec![
+            CardType::World,
+            CardType::Development,
+        ]
+    }
+}
+
+impl utils::Variants for PowerType {
+    fn variants() -> Vec<PowerType> {
+        vec![
+            PowerType::Explore,
+            PowerType::Develop,
+            PowerType::Settle,
+        ]
+    }
+}
+
+/// What a player can look for with the Search action.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum SearchCategory {
+    /// A development giving +1 or +2 military.
+    MilitaryDevelopment,
+    /// A windfall military world with a defense of 1 or 2.
+    MilitaryWindfall,
+    /// A windfall non-military world costing 1 or 2.
+    PeacefulWindfall,
+    /// A world with the Uplift attribute.
+    Uplift,
+    /// A world that produces or windfalls Alien Technology.
+    AlienTechnology,
+    /// A military world with a defense of 5 or more.
+    MilitaryWorldFivePlus,
+    /// A development that costs 6.
+    SixCostDevelopment,
+    /// A card with a takeover power.
+    Takeover,
+}
+
+impl SearchCategory {
+    pub fn matches(&self, card: &Card) -> bool {
+        let is_world = card.card_type == CardType::World;
+        let windfall = matches!(card.produces, Some((Production::Windfall, _)));
+
+        match *self {
+            SearchCategory::MilitaryDevelopment =>
+                card.card_type == CardType::Development &&
+                card.powers.iter().any(|p| { matches!(*p, Power::SettleMilitaryBonus(1) | Power::SettleMilitaryBonus(2)) }),
+            SearchCategory::MilitaryWindfall =>
+                is_world && windfall && matches!(card.cost, Cost::Military(1) | Cost::Military(2)),
+            SearchCategory::PeacefulWindfall =>
+                is_world && windfall && matches!(card.cost, Cost::Trade(1) | Cost::Trade(2)),
+            SearchCategory::Uplift => is_world && card.attributes.contains(&Attribute::Uplift),
+            SearchCategory::AlienTechnology =>
+                is_world && matches!(card.produces, Some((_, Good::AlienTechnology))),
+            SearchCategory::MilitaryWorldFivePlus => is_world && matches!(card.cost, Cost::Military(n) if n >= 5),
+            SearchCategory::SixCostDevelopment =>
+                card.card_type == CardType::Development && card.cost == Cost::Trade(6),
+            SearchCategory::Takeover =>
+                card.powers.iter().any(|p| { matches!(*p, Power::TakeoverRebel | Power::TakeoverImperium) }),
+        }
+    }
+}
+
+impl fmt::Display for SearchCategory {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(match *self {
+            SearchCategory::MilitaryDevelopment => "A development with +1 or +2 military",
+            SearchCategory::MilitaryWindfall => "A military windfall world with 1 or 2 defense",
+            SearchCategory::PeacefulWindfall => "A non-military windfall world costing 1 or 2",
+            SearchCategory::Uplift => "An Uplift world",
+            SearchCategory::AlienTechnology => "An Alien Technology world",
+            SearchCategory::MilitaryWorldFivePlus => "A military world with 5 or more defense",
+            SearchCategory::SixCostDevelopment => "A 6 cost development",
+            SearchCategory::Takeover => "A card with a takeover power",
+        })
+    }
+}
+
+impl utils::Variants for SearchCategory {
+    fn variants() -> Vec<SearchCategory> {
+        vec![
+            SearchCategory::MilitaryDevelopment,
+            SearchCategory::MilitaryWindfall,
+            SearchCategory::PeacefulWindfall,
+            SearchCategory::Uplift,
+            SearchCategory::AlienTechnology,
+            SearchCategory::MilitaryWorldFivePlus,
+            SearchCategory::SixCostDevelopment,
+            SearchCategory::Takeover,
+        ]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
-enum Points {
+pub enum Points {
     Simple(i32),
     TableauConditions(i32, Vec<Condition>),
     Military,
@@ -101,13 +358,14 @@ enum Points {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum Condition {
+pub enum Condition {
     CardType(CardType),
     PowerType(PowerType),
     Named(String),
     Attribute(Attribute),
     MinCost(Cost),
     // MaxCost(Cost),
+    MilitaryWorld,
     Produces(Production, Good),
     Good(Good),
     // Production(Production),
@@ -123,6 +381,9 @@ pub struct Card {
     pub produces: Option<(Production, Good)>,
     pub powers: Vec<Power>,
     pub attributes: Vec<Attribute>,
+    pub expansion: Expansion,
+    /// The card is only used in games with at least this many players.
+    pub min_players: usize,
 }
 
 impl Card {
@@ -167,6 +428,20 @@ impl Card {
         self.attributes.push(attribute);
         self
     }
+
+    fn expansion(mut self, expansion: Expansion) -> Card {
+        self.expansion = expansion;
+        self
+    }
+
+    fn min_players(mut self, min_players: usize) -> Card {
+        self.min_players = min_players;
+        self
+    }
+
+    pub fn is_available_for(&self, num_players: usize) -> bool {
+        num_players >= self.min_players
+    }
 }
 
 impl fmt::Display for Card {
@@ -185,7 +460,7 @@ impl fmt::Display for Card {
         });
 
         let mut has_complex = false;
-        for points in (&self.victory_points).iter() {
+        for points in self.victory_points.iter() {
             match points {
                 &Points::Simple(n) => { parts.push(format!("{{{} VPs}}", n)); },
                 _ => { has_complex = true; },
@@ -205,7 +480,47 @@ impl fmt::Display for Card {
             parts.push(format!("{:?}", power));
         }
 
-        fmt.write_str(parts.connect(" ").as_slice()).ok().expect("Could not format string.");
-        Result::Ok(())
+        fmt.write_str(&parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_deck_for, max_players, Expansion};
+
+    #[test]
+    fn fifth_player_start_worlds_need_five_players() {
+        let names = ["Doomed World", "Separatist Colony", "Ancient Race", "Damaged Alien Factory"];
+        let has = |num_players: usize, name: &str| {
+            get_deck_for(&[Expansion::GatheringStorm], num_players).iter().any(|card| { card.name == name })
+        };
+
+        for name in names.iter() {
+            assert!(!has(2, name));
+            assert!(has(5, name));
+        }
+        assert!(has(2, "Star Nomad Lair"));
+    }
+
+    #[test]
+    fn sixth_player_start_worlds_need_six_players() {
+        let expansions = [Expansion::GatheringStorm, Expansion::RebelVsImperium];
+        let has = |num_players: usize, name: &str| {
+            get_deck_for(&expansions, num_players).iter().any(|card| { card.name == name })
+        };
+
+        for name in ["Rebel Cantina", "Galactic Developers", "Imperium Warlord"].iter() {
+            assert!(!has(5, name));
+            assert!(has(6, name));
+        }
+    }
+
+    #[test]
+    fn gathering_storm_and_rebel_vs_imperium_add_a_seat_each() {
+        assert_eq!(max_players(&[]), 4);
+        assert_eq!(max_players(&[Expansion::GatheringStorm]), 5);
+        assert_eq!(max_players(&[Expansion::GatheringStorm, Expansion::BrinkOfWar]), 5);
+        assert_eq!(max_players(&[Expansion::GatheringStorm, Expansion::RebelVsImperium]), 6);
+        assert_eq!(max_players(&[Expansion::GatheringStorm, Expansion::RebelVsImperium, Expansion::BrinkOfWar]), 6);
     }
 }
@@ -0,0 +1,103 @@
+use super::*;
+
+/// The cards added by the Rebel vs Imperium expansion, including the start
+/// worlds for a sixth player. These bring the takeover powers: Imperium cards
+/// attack Rebel military worlds, and Rebel cards attack the military worlds
+/// of Imperium players. Trade, consume and produce powers are left off.
+pub fn get_cards() -> Vec<Card> {
+    let cards = vec![
+        // Start worlds.
+        Card::new("Rebel Cantina")
+            .card_type(CardType::World)
+            .military_cost(0)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_attribute(Attribute::Rebel)
+            .add_power(Power::SettleMilitaryIfAttribute(1, Attribute::Rebel))
+            .min_players(6),
+
+        Card::new("Galactic Developers")
+            .card_type(CardType::World)
+            .trade_cost(0)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_power(Power::DevelopDraw(1))
+            .min_players(6),
+
+        Card::new("Imperium Warlord")
+            .card_type(CardType::World)
+            .military_cost(0)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Starter)
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryIfAttribute(1, Attribute::Rebel))
+            .add_power(Power::TakeoverRebel)
+            .min_players(6),
+
+        // Worlds.
+        Card::new("Rebel Sympathizers")
+            .card_type(CardType::World)
+            .military_cost(2)
+            .add_points(Points::Simple(1))
+            .produces(Production::Windfall, Good::Novelty)
+            .add_attribute(Attribute::Rebel),
+
+        Card::new("Rebel Fuel Refinery")
+            .card_type(CardType::World)
+            .military_cost(3)
+            .add_points(Points::Simple(2))
+            .produces(Production::Produces, Good::RareElements)
+            .add_attribute(Attribute::Rebel),
+
+        Card::new("Imperium Armaments World")
+            .card_type(CardType::World)
+            .military_cost(4)
+            .add_points(Points::Simple(2))
+            .produces(Production::Produces, Good::RareElements)
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryBonus(1)),
+
+        // Developments.
+        Card::new("Imperium Invasion Fleet")
+            .card_type(CardType::Development)
+            .trade_cost(3)
+            .add_points(Points::Simple(1))
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryBonus(2))
+            .add_power(Power::TakeoverRebel),
+
+        Card::new("Rebel Alliance")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(2, vec![
+                Condition::MilitaryWorld,
+                Condition::Attribute(Attribute::Rebel),
+            ]))
+            .add_points(Points::TableauConditions(1, vec![
+                Condition::MilitaryWorld,
+                Condition::Not(Box::new(Condition::Attribute(Attribute::Rebel))),
+            ]))
+            .add_attribute(Attribute::Rebel)
+            .add_power(Power::SettleMilitaryBonus(2))
+            .add_power(Power::TakeoverImperium),
+
+        Card::new("Imperium Lords")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(2, vec![Condition::Attribute(Attribute::Imperium)]))
+            .add_points(Points::TableauConditions(1, vec![
+                Condition::MilitaryWorld,
+                Condition::Not(Box::new(Condition::Attribute(Attribute::Imperium))),
+            ]))
+            .add_attribute(Attribute::Imperium)
+            .add_power(Power::SettleMilitaryBonus(1)),
+
+        Card::new("Pan-Galactic Security Council")
+            .card_type(CardType::Development)
+            .trade_cost(6)
+            .add_points(Points::TableauConditions(1, vec![Condition::CardType(CardType::Development)]))
+            .add_power(Power::TakeoverDefense(3)),
+    ];
+
+    cards.into_iter().map(|card| { card.expansion(Expansion::RebelVsImperium) }).collect()
+}
@@ -0,0 +1,36 @@
+use super::{Card, CardType, Cost};
+
+/// A development with a trade cost and nothing else. Powers and attributes
+/// can be added with struct update syntax.
+pub fn development(name: &str, cost: i32) -> Card {
+    Card {
+        name: name.to_string(),
+        card_type: CardType::Development,
+        cost: Cost::Trade(cost),
+        ..Default::default()
+    }
+}
+
+/// A non-military world with a trade cost and nothing else.
+pub fn world(name: &str, cost: i32) -> Card {
+    Card {
+        name: name.to_string(),
+        card_type: CardType::World,
+        cost: Cost::Trade(cost),
+        ..Default::default()
+    }
+}
+
+/// A military world with a defense and nothing else.
+pub fn military_world(name: &str, defense: i32) -> Card {
+    Card {
+        cost: Cost::Military(defense),
+        ..world(name, 0)
+    }
+}
+
+/// `count` developments costing `cost`, named "Card 0", "Card 1" and so on,
+/// so tests can tell them apart.
+pub fn developments(count: usize, cost: i32) -> Vec<Card> {
+    (0..count).map(|i| { development(&format!("Card {}", i), cost) }).collect()
+}
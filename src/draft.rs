@@ -0,0 +1,140 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+use crate::cards;
+use crate::error::{self, RftgError};
+use crate::score;
+
+/// How many cards are dealt into each pack.
+pub const PACK_SIZE: usize = 5;
+
+/// Drafting personal decks before the game. The deck is dealt out in packs,
+/// every player picks a card from the pack in front of them at the same
+/// time, and the packs are passed to the left. Once the packs are empty new
+/// ones are dealt, until there aren't enough cards for every player.
+#[derive(Debug, Clone)]
+pub struct Draft {
+    packs: Vec<Vec<cards::Card>>,
+    picked: Vec<Vec<cards::Card>>,
+    remaining: Vec<cards::Card>,
+    pack_size: usize,
+}
+
+impl Draft {
+    pub fn new(mut deck: Vec<cards::Card>, num_players: usize, pack_size: usize) -> Draft {
+        deck.shuffle(&mut thread_rng());
+
+        let mut draft = Draft {
+            packs: vec![vec![]; num_players],
+            picked: vec![vec![]; num_players],
+            remaining: deck,
+            pack_size,
+        };
+        draft.deal();
+        draft
+    }
+
+    /// The pack a player is choosing from now.
+    pub fn pack(&self, player: usize) -> &[cards::Card] {
+        &self.packs[player]
+    }
+
+    /// The cards a player has drafted so far.
+    pub fn picked(&self, player: usize) -> &[cards::Card] {
+        &self.picked[player]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.packs.iter().all(|pack| { pack.is_empty() })
+    }
+
+    /// Every player takes a card, by index into their pack, and passes the
+    /// rest of the pack to the left.
+    pub fn pick(&mut self, picks: &[usize]) -> error::Result<()> {
+        if picks.len() != self.packs.len() {
+            return Err(RftgError::IllegalMove(format!("Every one of the {} players must pick.", self.packs.len())));
+        }
+        for (pack, &index) in self.packs.iter().zip(picks.iter()) {
+            if index >= pack.len() {
+                return Err(RftgError::IllegalMove(format!("There is no card {} in the pack.", index + 1)));
+            }
+        }
+
+        for (player, &index) in picks.iter().enumerate() {
+            let card = self.packs[player].remove(index);
+            self.picked[player].push(card);
+        }
+
+        // Every pack shrinks by one card a pick, so they all run out together.
+        self.packs.rotate_right(1);
+        if self.is_done() {
+            self.deal();
+        }
+        Ok(())
+    }
+
+    /// The drafted decks, one for each player.
+    pub fn into_decks(self) -> Vec<Vec<cards::Card>> {
+        self.picked
+    }
+
+    fn deal(&mut self) {
+        if self.remaining.len() < self.packs.len() * self.pack_size {
+            return;
+        }
+        for pack in self.packs.iter_mut() {
+            let at = self.remaining.len() - self.pack_size;
+            *pack = self.remaining.split_off(at);
+        }
+    }
+}
+
+/// A simple drafting choice for computer players: the card with the most
+/// printed victory points for its cost, preferring cards with powers.
+pub fn ai_pick(pack: &[cards::Card]) -> usize {
+    let value = |card: &cards::Card| -> i32 {
+        let cost = score::cost_value(&card.cost);
+        4 * score::flat_points(card) + 3 * card.powers.len() as i32 - cost
+    };
+
+    (0..pack.len()).max_by_key(|&i| { value(&pack[i]) }).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{self, test_cards};
+    use super::Draft;
+
+    #[test]
+    fn every_card_is_dealt_once() {
+        // Two rounds of packs use 18 cards, leaving too few for a third.
+        let mut draft = Draft::new(test_cards::developments(20, 1), 3, 3);
+        while !draft.is_done() {
+            draft.pick(&[0, 0, 0]).unwrap();
+        }
+
+        let mut names: Vec<String> = draft.into_decks().into_iter()
+            .inspect(|picked| { assert_eq!(picked.len(), 6) })
+            .flatten()
+            .map(|card| { card.name })
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 18);
+        assert!(names.iter().all(|name| { test_cards::developments(20, 1).iter().any(|card| { card.name == *name }) }));
+    }
+
+    #[test]
+    fn packs_pass_to_the_left() {
+        let mut draft = Draft::new(test_cards::developments(9, 1), 3, 3);
+        let packs: Vec<Vec<cards::Card>> = (0..3).map(|player| { draft.pack(player).to_vec() }).collect();
+        draft.pick(&[0, 1, 2]).unwrap();
+
+        // Each player picked the card at their own index.
+        for (player, pack) in packs.iter().enumerate() {
+            let mut passed = pack.clone();
+            assert_eq!(draft.picked(player), &[passed.remove(player)][..]);
+            assert_eq!(draft.pack((player + 1) % 3), &passed[..]);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::{error, fmt, io, result};
+
+#[derive(Debug)]
+pub enum RftgError {
+    /// The move isn't allowed by the rules in the current state.
+    IllegalMove(String),
+    /// More cards were needed than were available.
+    InsufficientCards { needed: usize, available: usize },
+    /// Reading from or writing to the terminal failed.
+    Io(io::Error),
+    /// The game data is inconsistent, such as a card missing from where it
+    /// should be.
+    InvalidData(String),
+}
+
+pub type Result<T> = result::Result<T, RftgError>;
+
+impl fmt::Display for RftgError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match *self {
+            RftgError::IllegalMove(ref reason) => write!(fmt, "Illegal move: {}", reason),
+            RftgError::InsufficientCards { needed, available } =>
+                write!(fmt, "Not enough cards: need {}, but only have {}.", needed, available),
+            RftgError::Io(ref err) => write!(fmt, "I/O error: {}", err),
+            RftgError::InvalidData(ref reason) => write!(fmt, "Invalid data: {}", reason),
+        }
+    }
+}
+
+impl error::Error for RftgError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            RftgError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RftgError {
+    fn from(err: io::Error) -> RftgError {
+        RftgError::Io(err)
+    }
+}
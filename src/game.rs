@@ -1,12 +1,15 @@
-extern crate rand;
-
 use std::fmt;
-use rand::{thread_rng, Rng};
+use rand::seq::SliceRandom;
+use rand::thread_rng;
 
-use cards;
-use utils;
+use crate::cards;
+use crate::error::{self, RftgError};
+use crate::goals;
+use crate::player;
+use crate::robot;
+use crate::utils::{self, Variants};
 
-#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Clone)]
 pub enum Phase {
     Explore,
     Develop,
@@ -15,7 +18,7 @@ pub enum Phase {
 
 impl fmt::Display for Phase {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        (self as &fmt::Debug).fmt(formatter)
+        fmt::Debug::fmt(self, formatter)
     }
 }
 
@@ -29,36 +32,1047 @@ impl utils::Variants for Phase {
     }
 }
 
+/// The action cards a player can choose from. The two explore cards both
+/// resolve during the explore phase, so a player who chooses both gets both
+/// bonuses. Search also resolves then, and each player can use it once per
+/// game instead of exploring.
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+pub enum Action {
+    ExploreSeeFive,
+    ExploreMix,
+    Search,
+    Develop,
+    Settle,
+}
+
+impl Action {
+    pub fn phase(&self) -> Phase {
+        match *self {
+            Action::ExploreSeeFive => Phase::Explore,
+            Action::ExploreMix => Phase::Explore,
+            Action::Search => Phase::Explore,
+            Action::Develop => Phase::Develop,
+            Action::Settle => Phase::Settle,
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Action::ExploreSeeFive => formatter.write_str("Explore (+5)"),
+            Action::ExploreMix => formatter.write_str("Explore (+1/+1, mix)"),
+            Action::Search => formatter.write_str("Search (once per game)"),
+            Action::Develop => formatter.write_str("Develop"),
+            Action::Settle => formatter.write_str("Settle"),
+        }
+    }
+}
+
+/// The phases played in a round, in order, given every player's action
+/// cards. Explore is played once however many explore cards were chosen. In
+/// the advanced game a Develop or Settle phase is played twice if anyone
+/// chose both of its cards.
+fn round_phases(choices: &[&[Action]]) -> Vec<Phase> {
+    let mut phases = vec![];
+
+    for phase in Phase::variants() {
+        let most = choices.iter()
+            .map(|chosen| { chosen.iter().filter(|action| { action.phase() == phase }).count() })
+            .max()
+            .unwrap_or(0);
+        let times = if phase == Phase::Explore { most.min(1) } else { most };
+        for _ in 0..times {
+            phases.push(phase.clone());
+        }
+    }
+
+    phases
+}
+
+/// What the game is waiting for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    /// Every player chooses their action cards for the round.
+    ChooseActions,
+    /// Every player takes their turn in a phase.
+    Phase(Phase),
+    /// Players over the hand limit discard before the next round.
+    EndOfRound,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Step::ChooseActions => formatter.write_str("choosing actions"),
+            Step::Phase(ref phase) => write!(formatter, "the {} phase", phase),
+            Step::EndOfRound => formatter.write_str("the end of the round"),
+        }
+    }
+}
+
+/// How far the current round has got.
+#[derive(Debug, Clone)]
+struct Round {
+    /// Each player's action cards, once they have chosen them.
+    chosen: Vec<Option<Vec<Action>>>,
+    /// The robot's action card, rolled once every player has chosen.
+    robot_action: Option<Action>,
+    /// The phases of this round, once every action card is known.
+    phases: Vec<Phase>,
+    /// How many of `phases` are over.
+    phases_done: usize,
+    /// Which players have finished their turn in the current phase.
+    finished: Vec<bool>,
+    robot_finished: bool,
+}
+
+impl Round {
+    fn new(num_players: usize) -> Round {
+        Round {
+            chosen: vec![None; num_players],
+            robot_action: None,
+            phases: vec![],
+            phases_done: 0,
+            finished: vec![false; num_players],
+            robot_finished: false,
+        }
+    }
+}
+
+/// The game ends after a round in which a tableau reaches this many cards.
+pub const END_TABLEAU_SIZE: usize = 12;
+
+impl utils::Variants for Action {
+    fn variants() -> Vec<Self> {
+        vec![
+            Action::ExploreSeeFive,
+            Action::ExploreMix,
+            Action::Search,
+            Action::Develop,
+            Action::Settle,
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Game {
+    /// Every card this game was set up with.
+    deck_list: Vec<cards::Card>,
     draw_pile: Vec<cards::Card>,
     discard_pile: Vec<cards::Card>,
+    /// The cards in the discard pile that were discarded face up, so every
+    /// player knows they are there.
+    public_discards: Vec<cards::Card>,
+    reshuffles: usize,
+    empty_draws: usize,
+    vp_chips: i32,
 }
 
+/// What card counting can tell from the piles: their sizes, how often the
+/// discard pile has been shuffled back in, and what is known to be in the
+/// discard pile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeckStats {
+    pub draw_pile: usize,
+    pub discard_pile: usize,
+    pub reshuffles: usize,
+    pub public_discards: usize,
+    pub by_type: Vec<(cards::CardType, usize)>,
+    pub by_good: Vec<(cards::Good, usize)>,
+    pub by_cost: Vec<(cards::Cost, usize)>,
+}
+
+/// The VP chip pool holds this many chips for each player.
+pub const VP_CHIPS_PER_PLAYER: i32 = 12;
+
 impl Game {
-    pub fn new(draw_pile: Vec<cards::Card>) -> Game {
+    /// Set up for a game with `num_players` and `expansions`: the deck
+    /// without cards that aren't used at this player count, and a VP chip
+    /// pool sized for it.
+    pub fn new(num_players: usize, expansions: &[cards::Expansion]) -> error::Result<Game> {
+        let max = cards::max_players(expansions);
+        if num_players == 0 || num_players > max {
+            return Err(RftgError::InvalidData(format!("Games need between 1 and {} players.", max)));
+        }
+        Ok(Game::from_deck(cards::get_deck_for(expansions, num_players), num_players))
+    }
+
+    /// Set up with a particular deck, such as one that was drafted.
+    pub fn from_deck(draw_pile: Vec<cards::Card>, num_players: usize) -> Game {
         let mut draw_pile = draw_pile;
         let mut rng = thread_rng();
-        rng.shuffle(&mut draw_pile);
+        draw_pile.shuffle(&mut rng);
         Game {
-            draw_pile: draw_pile,
+            deck_list: draw_pile.clone(),
+            draw_pile,
             discard_pile: vec![],
+            public_discards: vec![],
+            reshuffles: 0,
+            empty_draws: 0,
+            vp_chips: VP_CHIPS_PER_PLAYER * num_players as i32,
         }
     }
 
-    pub fn draw(&mut self) -> cards::Card {
+    pub fn vp_chips_left(&self) -> i32 {
+        self.vp_chips
+    }
+
+    /// Take up to `n` chips from the pool, returning how many there were.
+    pub fn take_vp_chips(&mut self, n: i32) -> i32 {
+        let taken = n.min(self.vp_chips).max(0);
+        self.vp_chips -= taken;
+        taken
+    }
+
+    /// Draw a card, reshuffling the discard pile if needed. Returns `None`
+    /// when every card is in a hand or a tableau, in which case no more cards
+    /// can be drawn until some are discarded. Both are counted, in
+    /// `reshuffles` and `empty_draws`, for frontends to report.
+    pub fn draw(&mut self) -> Option<cards::Card> {
         if self.draw_pile.is_empty() {
             if self.discard_pile.is_empty() {
-                panic!("Out of cards!");
+                self.empty_draws += 1;
+                return None;
             }
-            println!("Shuffle!");
             let mut rng = thread_rng();
+            self.reshuffles += 1;
+            self.public_discards.clear();
             self.draw_pile.append(&mut self.discard_pile);
-            rng.shuffle(&mut self.draw_pile);
+            self.draw_pile.shuffle(&mut rng);
+        }
+        self.draw_pile.pop()
+    }
+
+    /// Flip cards, discarding them, until one matches. Gives up after
+    /// flipping as many cards as there are in both piles.
+    pub fn flip_until<F>(&mut self, matches: F) -> Option<cards::Card>
+        where F: Fn(&cards::Card) -> bool,
+    {
+        for _ in 0..(self.draw_pile.len() + self.discard_pile.len()) {
+            match self.draw() {
+                Some(card) => {
+                    if matches(&card) {
+                        return Some(card);
+                    }
+                    self.discard_face_up(card);
+                },
+                None => break,
+            }
         }
-        self.draw_pile.pop().unwrap()
+        None
+    }
+
+    /// The number of cards left to draw before the next reshuffle.
+    pub fn draw_pile_len(&self) -> usize {
+        self.draw_pile.len()
+    }
+
+    pub fn discard_pile_len(&self) -> usize {
+        self.discard_pile.len()
+    }
+
+    /// Every card this game was set up with, wherever they are now.
+    pub fn deck_list(&self) -> &[cards::Card] {
+        &self.deck_list
+    }
+
+    /// How many times the discard pile has been shuffled into the draw pile.
+    pub fn reshuffles(&self) -> usize {
+        self.reshuffles
+    }
+
+    /// How many times a card couldn't be drawn because both piles were empty.
+    pub fn empty_draws(&self) -> usize {
+        self.empty_draws
     }
 
+    /// The cards every player has seen go into the discard pile since the
+    /// last reshuffle.
+    pub fn public_discards(&self) -> &[cards::Card] {
+        &self.public_discards
+    }
+
+    pub fn stats(&self) -> DeckStats {
+        let known = &self.public_discards;
+        let count = |f: &dyn Fn(&cards::Card) -> bool| { known.iter().filter(|card| { f(card) }).count() };
+
+        let by_type = cards::CardType::variants().into_iter()
+            .map(|card_type| { let n = count(&|card| { card.card_type == card_type }); (card_type, n) })
+            .collect();
+        let by_good = cards::Good::variants().into_iter()
+            .map(|good| { let n = count(&|card| { card.produces.as_ref().is_some_and(|p| { p.1 == good }) }); (good, n) })
+            .collect();
+
+        let mut by_cost: Vec<(cards::Cost, usize)> = vec![];
+        for card in known.iter() {
+            match by_cost.iter_mut().find(|entry| { entry.0 == card.cost }) {
+                Some(entry) => entry.1 += 1,
+                None => by_cost.push((card.cost.clone(), 1)),
+            }
+        }
+        by_cost.sort_by_key(|entry| {
+            match entry.0 {
+                cards::Cost::Free => (0, 0),
+                cards::Cost::Trade(n) => (1, n),
+                cards::Cost::Military(n) => (2, n),
+            }
+        });
+
+        DeckStats {
+            draw_pile: self.draw_pile.len(),
+            discard_pile: self.discard_pile.len(),
+            reshuffles: self.reshuffles,
+            public_discards: known.len(),
+            by_type,
+            by_good,
+            by_cost,
+        }
+    }
+
+    /// Discard a card face down.
     pub fn discard(&mut self, card: cards::Card) {
         self.discard_pile.push(card);
     }
+
+    /// Discard a card that every player has seen.
+    pub fn discard_face_up(&mut self, card: cards::Card) {
+        self.public_discards.push(card.clone());
+        self.discard_pile.push(card);
+    }
+}
+
+/// A single decision by one player. `player` is an index into
+/// `GameState::players`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Move {
+    /// Choose this round's action cards, all at once.
+    ChooseActions { player: usize, actions: Vec<Action> },
+    /// Discard a card from hand before exploring, to see one more card.
+    ExploreDiscard { player: usize, index: usize },
+    /// Draw the cards to explore.
+    Explore { player: usize },
+    /// Keep explored cards, as indices into the explored cards.
+    ExploreKeep { player: usize, keep: Vec<usize> },
+    /// Discard from the hand after mixing in explored cards.
+    ExploreMixDiscard { player: usize, discard: Vec<usize> },
+    /// Draw the bonus cards from `DevelopDraw` powers.
+    DevelopDraw { player: usize },
+    /// Place a development, or pass.
+    Develop { player: usize, placement: Option<player::Placement> },
+    /// Discard a card from the tableau or hand for extra military this phase.
+    MilitaryDiscard { player: usize, discard: player::MilitaryDiscard },
+    /// Settle a world, or pass.
+    Settle { player: usize, choice: Option<(player::Placement, player::SettleMethod)> },
+    /// Use the Search action, flipping cards until one matches `category`.
+    Search { player: usize, category: cards::SearchCategory },
+    /// Keep the card found by searching, or discard it and take the next
+    /// match.
+    SearchKeep { player: usize, keep: bool },
+    /// Attack `card`, a military world in player `target`'s tableau, instead
+    /// of settling.
+    Takeover { player: usize, target: usize, card: cards::Card },
+    /// Discard down to the hand limit at the end of the round.
+    HandLimitDiscard { player: usize, discard: Vec<usize> },
+}
+
+impl Move {
+    pub fn player(&self) -> usize {
+        match *self {
+            Move::ChooseActions { player, .. } |
+            Move::ExploreDiscard { player, .. } |
+            Move::Explore { player, .. } |
+            Move::ExploreKeep { player, .. } |
+            Move::ExploreMixDiscard { player, .. } |
+            Move::DevelopDraw { player } |
+            Move::Develop { player, .. } |
+            Move::MilitaryDiscard { player, .. } |
+            Move::Settle { player, .. } |
+            Move::Takeover { player, .. } |
+            Move::Search { player, .. } |
+            Move::SearchKeep { player, .. } |
+            Move::HandLimitDiscard { player, .. } => player,
+        }
+    }
+}
+
+/// A world in an opponent's tableau that a player could attack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakeoverOption {
+    pub target: usize,
+    pub card: cards::Card,
+    pub attack: i32,
+    pub defense: i32,
+}
+
+impl TakeoverOption {
+    /// Takeovers succeed when the attack at least matches the defense.
+    pub fn succeeds(&self) -> bool {
+        self.attack >= self.defense
+    }
+}
+
+impl fmt::Display for TakeoverOption {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "Take over {} from player {} ({} military against {} defense)",
+               self.card.name, self.target + 1, self.attack, self.defense)
+    }
+}
+
+/// Everything about a game in progress. Moves produce a new state rather
+/// than changing this one, so states can be kept for lookahead or undo.
+#[derive(Debug, Clone)]
+pub struct GameState {
+    game: Game,
+    players: Vec<player::Player>,
+    advanced: bool,
+    goals: Option<goals::Goals>,
+    prestige: bool,
+    robot: Option<robot::Robot>,
+    /// Each player's own draw and discard piles, after a draft.
+    personal: Option<Vec<Game>>,
+    round: Round,
+}
+
+impl GameState {
+    /// Shuffle the deck for this many players and expansions, and deal an
+    /// opening hand to each player.
+    pub fn new(num_players: usize, expansions: &[cards::Expansion]) -> error::Result<GameState> {
+        Ok(GameState::with_game(Game::new(num_players, expansions)?, num_players))
+    }
+
+    pub(crate) fn with_game(mut game: Game, num_players: usize) -> GameState {
+        let mut players = vec![];
+
+        for _ in 0..num_players {
+            let mut player = player::Player::new();
+            player.draw_up_to(&mut game, 3);
+            players.push(player);
+        }
+
+        GameState {
+            game,
+            players,
+            advanced: false,
+            goals: None,
+            prestige: false,
+            robot: None,
+            personal: None,
+            round: Round::new(num_players),
+        }
+    }
+
+    /// Start a game from drafted decks, where each player draws from and
+    /// discards to their own piles.
+    pub fn drafted(decks: Vec<Vec<cards::Card>>) -> GameState {
+        let num_players = decks.len();
+        let mut state = GameState::with_game(Game::from_deck(vec![], num_players), 0);
+        let mut piles = vec![];
+
+        for deck in decks {
+            let mut game = Game::from_deck(deck, num_players);
+            let mut player = player::Player::new();
+            player.draw_up_to(&mut game, 3);
+            state.players.push(player);
+            piles.push(game);
+        }
+
+        state.personal = Some(piles);
+        state.round = Round::new(num_players);
+        state
+    }
+
+    /// Play the two player advanced game, where each player chooses two
+    /// action cards a round.
+    pub fn advanced(mut self, advanced: bool) -> GameState {
+        self.advanced = advanced;
+        self
+    }
+
+    pub fn is_advanced(&self) -> bool {
+        self.advanced
+    }
+
+    /// Play with the Brink of War Search action and prestige leader bonus.
+    pub fn prestige(mut self, prestige: bool) -> GameState {
+        self.prestige = prestige;
+        self
+    }
+
+    /// Play the solo game against a robot opponent.
+    pub fn robot(mut self, robot: robot::Robot) -> GameState {
+        self.robot = Some(robot);
+        self
+    }
+
+    pub fn get_robot(&self) -> Option<&robot::Robot> {
+        self.robot.as_ref()
+    }
+
+    /// The robot's action card this round, once every player has chosen.
+    pub fn robot_action(&self) -> Option<&Action> {
+        self.round.robot_action.as_ref()
+    }
+
+    /// The state after the robot plays the current phase, and what it did.
+    /// Without a robot there is nothing to do.
+    pub fn play_robot_phase(&self) -> error::Result<(GameState, Option<robot::RobotTurn>)> {
+        let phase = match self.step() {
+            Step::Phase(phase) => phase,
+            step => return Err(RftgError::IllegalMove(format!("The robot can't play during {}.", step))),
+        };
+        if self.round.robot_finished {
+            return Err(RftgError::IllegalMove("The robot has already played this phase.".to_string()));
+        }
+
+        // Like a player's, the robot's card only counts the first time its
+        // phase is played.
+        let chose = self.round.robot_action.as_ref().is_some_and(|action| { action.phase() == phase }) &&
+            !self.round.phases[..self.round.phases_done].contains(&phase);
+
+        let mut next = self.clone();
+        let turn = match next.robot {
+            Some(ref mut robot) => Some(robot.play_phase(&mut next.game, &phase, chose)),
+            None => None,
+        };
+        next.round.robot_finished = true;
+        Ok((next, turn))
+    }
+
+    /// Whether the game ends with this round, because a tableau is full or
+    /// the VP chips have run out.
+    pub fn is_over(&self) -> bool {
+        self.game.vp_chips_left() <= 0 ||
+            self.players.iter().any(|p| { p.tableau().len() >= END_TABLEAU_SIZE }) ||
+            self.robot.as_ref().is_some_and(|robot| { robot.tableau().len() >= END_TABLEAU_SIZE })
+    }
+
+    /// Play with goal tiles.
+    pub fn goals(mut self, goals: goals::Goals) -> GameState {
+        self.goals = Some(goals);
+        self
+    }
+
+    pub fn goal_tiles(&self) -> &[goals::GoalTile] {
+        match self.goals {
+            Some(ref goals) => goals.tiles(),
+            None => &[],
+        }
+    }
+
+    /// A player's victory points if the game ended now, including goals.
+    pub fn score(&self, player: usize) -> i32 {
+        let goal_points = self.goals.as_ref().map_or(0, |goals| { goals.points(player) });
+        self.players[player].score() + goal_points
+    }
+
+    /// The state once the round is over, and the prestige leader, who takes
+    /// a VP chip when playing with prestige. Nobody leads while the most
+    /// prestige is tied or zero. Every phase must be over, and every player
+    /// within the hand limit.
+    pub fn end_round(&self) -> error::Result<(GameState, Option<usize>)> {
+        if self.step() != Step::EndOfRound {
+            return Err(RftgError::IllegalMove(format!("The round can't end during {}.", self.step())));
+        }
+        if let Some(player) = self.players.iter().position(|p| { p.hand_limit_excess() > 0 }) {
+            return Err(RftgError::IllegalMove(format!("Player {} is over the hand limit.", player + 1)));
+        }
+
+        let mut next = self.clone();
+        let most = next.players.iter().map(|p| { p.prestige() }).max().unwrap_or(0);
+        let leaders: Vec<usize> = (0..next.players.len())
+            .filter(|&i| { next.players[i].prestige() == most })
+            .collect();
+
+        let leader = if self.prestige && most > 0 && leaders.len() == 1 { Some(leaders[0]) } else { None };
+        if let Some(leader) = leader {
+            let chips = next.game.take_vp_chips(1);
+            next.players[leader].gain_vp_chips(chips);
+        }
+        next.round = Round::new(next.players.len());
+        Ok((next, leader))
+    }
+
+    /// The state once a phase is over, with goals handed out, and the
+    /// changes in who holds which goal. Every player, and the robot, must
+    /// have finished their turn.
+    pub fn end_phase(&self) -> error::Result<(GameState, Vec<goals::Claim>)> {
+        let step = self.step();
+        if !matches!(step, Step::Phase(_)) {
+            return Err(RftgError::IllegalMove(format!("There is no phase to end during {}.", step)));
+        }
+        if let Some(player) = self.round.finished.iter().position(|finished| { !finished }) {
+            return Err(RftgError::IllegalMove(format!("Player {} hasn't finished {}.", player + 1, step)));
+        }
+        if self.robot.is_some() && !self.round.robot_finished {
+            return Err(RftgError::IllegalMove(format!("The robot hasn't played {}.", step)));
+        }
+
+        let mut next = self.clone();
+        let claims = match next.goals {
+            Some(ref mut goals) => goals.evaluate(&next.players),
+            None => vec![],
+        };
+        next.round.phases_done += 1;
+        next.round.finished = vec![false; next.players.len()];
+        next.round.robot_finished = false;
+        Ok((next, claims))
+    }
+
+    /// What the game is waiting for.
+    pub fn step(&self) -> Step {
+        if self.round.chosen.iter().any(|chosen| { chosen.is_none() }) {
+            return Step::ChooseActions;
+        }
+        match self.round.phases.get(self.round.phases_done) {
+            Some(phase) => Step::Phase(phase.clone()),
+            None => Step::EndOfRound,
+        }
+    }
+
+    /// The action cards `player` chose this round, once they have.
+    pub fn chosen_actions(&self, player: usize) -> Option<&[Action]> {
+        self.round.chosen.get(player).and_then(|chosen| { chosen.as_deref() })
+    }
+
+    /// The action cards that give `player` a bonus in the current phase.
+    /// When a phase is played twice, a player who chose its card once only
+    /// gets the bonus the first time.
+    pub fn phase_actions(&self, player: usize) -> Vec<Action> {
+        let phase = match self.step() {
+            Step::Phase(phase) => phase,
+            _ => return vec![],
+        };
+        let chosen = match self.chosen_actions(player) {
+            Some(chosen) => chosen,
+            None => return vec![],
+        };
+
+        let mine = chosen.iter().filter(|action| { action.phase() == phase }).cloned();
+        if phase == Phase::Explore {
+            return mine.collect();
+        }
+        let played = self.round.phases[..self.round.phases_done].iter().filter(|&p| { *p == phase }).count();
+        mine.skip(played).take(1).collect()
+    }
+
+    /// Whether `player` has finished their turn in the current phase.
+    pub fn has_finished_phase(&self, player: usize) -> bool {
+        self.round.finished.get(player).copied().unwrap_or(false)
+    }
+
+    /// Whether making this move shows cards that were not known before, so
+    /// that it can't be taken back.
+    pub fn reveals_information(&self, mv: &Move) -> bool {
+        match *mv {
+            Move::ChooseActions { .. } |
+            Move::Explore { .. } |
+            Move::DevelopDraw { .. } |
+            Move::Search { .. } |
+            Move::SearchKeep { keep: false, .. } => true,
+            Move::Settle { player, choice: Some(_) } => self.phase_actions(player).contains(&Action::Settle),
+            _ => false,
+        }
+    }
+
+    pub fn actions_per_round(&self) -> usize {
+        if self.advanced { 2 } else { 1 }
+    }
+
+    /// The action cards that `player` can add to those already `chosen`
+    /// this round. Empty once enough have been chosen.
+    ///
+    /// The two explore cards are separate cards, so in the advanced game a
+    /// player can choose both. Search replaces exploring, so it can't be
+    /// chosen with either. In the advanced game each player also has a
+    /// second Develop and a second Settle card.
+    pub fn action_options(&self, player: usize, chosen: &[Action]) -> Vec<Action> {
+        if chosen.len() >= self.actions_per_round() {
+            return vec![];
+        }
+
+        let copies = if self.advanced { 2 } else { 1 };
+        Action::variants().into_iter()
+            .filter(|action| { *action != Action::Search || (self.prestige && !self.players[player].has_searched()) })
+            .filter(|action| {
+                match *action {
+                    Action::Search => !chosen.iter().any(|c| { c.phase() == Phase::Explore }),
+                    Action::ExploreSeeFive | Action::ExploreMix => {
+                        !chosen.contains(action) && !chosen.contains(&Action::Search)
+                    },
+                    _ => chosen.iter().filter(|&c| { c == action }).count() < copies,
+                }
+            })
+            .collect()
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// The piles a player draws from and discards to.
+    pub fn game_for(&self, player: usize) -> &Game {
+        match self.personal {
+            Some(ref piles) => &piles[player],
+            None => &self.game,
+        }
+    }
+
+    pub fn is_drafted(&self) -> bool {
+        self.personal.is_some()
+    }
+
+    pub fn players(&self) -> &[player::Player] {
+        &self.players
+    }
+
+    pub fn player(&self, index: usize) -> &player::Player {
+        &self.players[index]
+    }
+
+    /// Every world in an opponent's tableau that `player` could attack
+    /// this phase, including takeovers that would fail.
+    pub fn takeover_options(&self, player: usize) -> Vec<TakeoverOption> {
+        let attacker = &self.players[player];
+        let mut options = vec![];
+
+        for (target, owner) in self.players.iter().enumerate() {
+            if target == player {
+                continue;
+            }
+            for card in owner.tableau().iter() {
+                if attacker.can_take_over(owner, card) {
+                    options.push(TakeoverOption {
+                        target,
+                        card: card.clone(),
+                        attack: attacker.military_against(card),
+                        defense: owner.takeover_defense(card),
+                    });
+                }
+            }
+        }
+
+        options
+    }
+
+    /// Resolve a takeover. A successful one moves the world between
+    /// tableaus; either way the attacker's settle phase is over.
+    fn take_over(&self, player: usize, target: usize, card: &cards::Card) -> error::Result<GameState> {
+        let option = match self.takeover_options(player).into_iter().find(|o| { o.target == target && o.card == *card }) {
+            Some(option) => option,
+            None => return Err(RftgError::IllegalMove(format!("You can't take over {}.", card.name))),
+        };
+
+        let mut next = self.clone();
+        if option.succeeds() {
+            let world = next.players[target].lose_world(card)?;
+            next.players[player].gain_world(world);
+        }
+        next.players[player].finish_settle();
+        Ok(next)
+    }
+
+    /// Check that `mv` can be made now: in the right step of the round, by a
+    /// player who hasn't already taken their turn.
+    fn check_turn(&self, mv: &Move) -> error::Result<()> {
+        let player = mv.player();
+        let expected = match *mv {
+            Move::ChooseActions { .. } => Step::ChooseActions,
+            Move::ExploreDiscard { .. } |
+            Move::Explore { .. } |
+            Move::ExploreKeep { .. } |
+            Move::ExploreMixDiscard { .. } |
+            Move::Search { .. } |
+            Move::SearchKeep { .. } => Step::Phase(Phase::Explore),
+            Move::DevelopDraw { .. } |
+            Move::Develop { .. } => Step::Phase(Phase::Develop),
+            Move::MilitaryDiscard { .. } |
+            Move::Settle { .. } |
+            Move::Takeover { .. } => Step::Phase(Phase::Settle),
+            Move::HandLimitDiscard { .. } => Step::EndOfRound,
+        };
+        let step = self.step();
+        if step != expected {
+            return Err(RftgError::IllegalMove(format!("That can't be done during {}.", step)));
+        }
+
+        match step {
+            Step::ChooseActions if self.round.chosen[player].is_some() => {
+                Err(RftgError::IllegalMove(format!("Player {} has already chosen their actions.", player + 1)))
+            },
+            Step::Phase(ref phase) if self.round.finished[player] => {
+                Err(RftgError::IllegalMove(format!("Player {} has already finished the {} phase.", player + 1, phase)))
+            },
+            Step::Phase(Phase::Explore) => {
+                let searching = self.phase_actions(player).contains(&Action::Search);
+                match *mv {
+                    Move::Search { .. } | Move::SearchKeep { .. } if !searching => {
+                        Err(RftgError::IllegalMove("You didn't choose to search.".to_string()))
+                    },
+                    Move::Search { .. } | Move::SearchKeep { .. } => Ok(()),
+                    _ if searching => Err(RftgError::IllegalMove("You chose to search instead of exploring.".to_string())),
+                    _ => Ok(()),
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a player's action cards. Once everyone has chosen, the robot
+    /// rolls its action and the round's phases are known.
+    fn choose_actions(&self, player: usize, actions: Vec<Action>) -> error::Result<GameState> {
+        for (i, action) in actions.iter().enumerate() {
+            if !self.action_options(player, &actions[..i]).contains(action) {
+                return Err(RftgError::IllegalMove(format!("You can't choose {}.", action)));
+            }
+        }
+        if !self.action_options(player, &actions).is_empty() {
+            return Err(RftgError::IllegalMove(format!("You must choose {} actions.", self.actions_per_round())));
+        }
+
+        let mut next = self.clone();
+        next.round.chosen[player] = Some(actions);
+        if next.round.chosen.iter().all(|chosen| { chosen.is_some() }) {
+            if next.robot.is_some() {
+                next.round.robot_action = Some(robot::Robot::roll_action());
+            }
+            let mut choices: Vec<&[Action]> = next.round.chosen.iter().flatten().map(|c| { c.as_slice() }).collect();
+            if let Some(ref action) = next.round.robot_action {
+                choices.push(std::slice::from_ref(action));
+            }
+            let phases = round_phases(&choices);
+            next.round.phases = phases;
+        }
+        Ok(next)
+    }
+
+    /// The state after making a move, or an error if the move is illegal.
+    /// Action bonuses come from the cards each player chose this round.
+    pub fn apply(&self, mv: Move) -> error::Result<GameState> {
+        let index = mv.player();
+        if index >= self.players.len() {
+            return Err(RftgError::IllegalMove(format!("There is no player {}.", index + 1)));
+        }
+        self.check_turn(&mv)?;
+
+        let actions = self.phase_actions(index);
+        let action = actions.first();
+        let searching = matches!(mv, Move::Search { .. });
+        let finishes = matches!(mv,
+                                Move::ExploreKeep { .. } |
+                                Move::ExploreMixDiscard { .. } |
+                                Move::SearchKeep { .. } |
+                                Move::Develop { .. } |
+                                Move::Settle { .. } |
+                                Move::Takeover { .. });
+
+        let mut next = self.clone();
+        let game = match next.personal {
+            Some(ref mut piles) => match piles.get_mut(mv.player()) {
+                Some(game) => game,
+                None => return Err(RftgError::IllegalMove(format!("There is no player {}.", mv.player() + 1))),
+            },
+            None => &mut next.game,
+        };
+        let player = match next.players.get_mut(mv.player()) {
+            Some(player) => player,
+            None => return Err(RftgError::IllegalMove(format!("There is no player {}.", mv.player() + 1))),
+        };
+
+        match mv {
+            Move::ChooseActions { player, actions } => return self.choose_actions(player, actions),
+            Move::ExploreDiscard { index, .. } => player.explore_discard(game, index)?,
+            Move::Explore { .. } => player.explore(game, &actions)?,
+            Move::ExploreKeep { keep, .. } => player.explore_keep(game, &keep)?,
+            Move::ExploreMixDiscard { discard, .. } => player.explore_mix_discard(game, &discard)?,
+            Move::DevelopDraw { .. } => player.develop_draw(game)?,
+            Move::Develop { placement, .. } => player.develop(game, placement.as_ref(), action)?,
+            Move::MilitaryDiscard { discard, .. } => player.military_discard(game, &discard)?,
+            Move::Settle { choice, .. } => {
+                let choice = choice.as_ref().map(|(placement, method)| { (placement, method) });
+                player.settle(game, choice, action)?
+            },
+            Move::HandLimitDiscard { discard, .. } => player.hand_limit_discard(game, &discard)?,
+            Move::Search { category, .. } => player.search(game, &category)?,
+            Move::SearchKeep { keep, .. } => player.search_keep(game, keep)?,
+            Move::Takeover { player: attacker, target, card } => next = self.take_over(attacker, target, &card)?,
+        }
+
+        // A search that finds nothing leaves nothing to keep.
+        if finishes || (searching && next.players[index].search_found().is_none()) {
+            next.round.finished[index] = true;
+        }
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{self, test_cards};
+    use crate::error::RftgError;
+    use crate::player;
+    use super::{Action, Game, GameState, Move, Phase, Step};
+
+    /// Two players, each holding three developments that cost `cost`.
+    fn two_players(cost: i32) -> GameState {
+        GameState::with_game(Game::from_deck(test_cards::developments(12, cost), 2), 2)
+    }
+
+    fn rebel_world() -> cards::Card {
+        cards::Card { attributes: vec![cards::Attribute::Rebel], ..test_cards::military_world("Rebel World", 2) }
+    }
+
+    /// Both players settling. Player 0 can take over Rebel worlds with
+    /// `military`; player 1 has a Rebel world and an ordinary one, both
+    /// with 2 defense.
+    fn settling(military: i32) -> GameState {
+        let mut state = two_players(1);
+        let attacker = cards::Card {
+            powers: vec![cards::Power::TakeoverRebel, cards::Power::SettleMilitaryBonus(military)],
+            ..test_cards::development("Attacker", 1)
+        };
+        state.players[0].gain_world(attacker);
+        state.players[1].gain_world(rebel_world());
+        state.players[1].gain_world(test_cards::military_world("Loyal World", 2));
+        choose(&choose(&state, 0, Action::Settle), 1, Action::Settle)
+    }
+
+    fn choose(state: &GameState, player: usize, action: Action) -> GameState {
+        state.apply(Move::ChooseActions { player, actions: vec![action] }).unwrap()
+    }
+
+    fn is_illegal(result: Result<GameState, RftgError>) -> bool {
+        matches!(result, Err(RftgError::IllegalMove(_)))
+    }
+
+    #[test]
+    fn moves_out_of_phase_are_rejected() {
+        let state = two_players(1);
+        assert!(is_illegal(state.apply(Move::Develop { player: 0, placement: None })));
+
+        let state = choose(&choose(&state, 0, Action::Develop), 1, Action::Settle);
+        assert_eq!(state.step(), Step::Phase(Phase::Develop));
+        assert!(is_illegal(state.apply(Move::Explore { player: 0 })));
+        assert!(is_illegal(state.apply(Move::Settle { player: 1, choice: None })));
+        assert!(is_illegal(state.apply(Move::HandLimitDiscard { player: 0, discard: vec![] })));
+        assert!(is_illegal(state.apply(Move::ChooseActions { player: 0, actions: vec![Action::Settle] })));
+    }
+
+    #[test]
+    fn duplicate_moves_are_rejected() {
+        let state = choose(&two_players(1), 0, Action::Develop);
+        assert!(is_illegal(state.apply(Move::ChooseActions { player: 0, actions: vec![Action::Settle] })));
+
+        let state = choose(&state, 1, Action::Develop);
+        let state = state.apply(Move::Develop { player: 0, placement: None }).unwrap();
+        assert!(state.has_finished_phase(0));
+        assert!(is_illegal(state.apply(Move::Develop { player: 0, placement: None })));
+        assert!(is_illegal(state.apply(Move::DevelopDraw { player: 0 })));
+    }
+
+    #[test]
+    fn too_many_or_too_few_actions_are_rejected() {
+        let state = two_players(1);
+        assert!(is_illegal(state.apply(Move::ChooseActions { player: 0, actions: vec![] })));
+        let actions = vec![Action::Develop, Action::Settle];
+        assert!(is_illegal(state.apply(Move::ChooseActions { player: 0, actions })));
+        assert!(is_illegal(state.apply(Move::ChooseActions { player: 0, actions: vec![Action::Search] })));
+    }
+
+    #[test]
+    fn both_explore_cards_can_be_chosen() {
+        let state = two_players(1).advanced(true);
+        assert!(state.action_options(0, &[Action::ExploreSeeFive]).contains(&Action::ExploreMix));
+
+        let actions = vec![Action::ExploreSeeFive, Action::ExploreMix];
+        let state = state.apply(Move::ChooseActions { player: 0, actions: actions.clone() }).unwrap();
+        let state = state.apply(Move::ChooseActions { player: 1, actions: vec![Action::Develop, Action::Settle] })
+            .unwrap();
+        assert_eq!(state.phase_actions(0), actions);
+
+        let counts = state.player(0).explore_counts(&state.phase_actions(0));
+        assert_eq!((counts.see, counts.keep, counts.mix), (2 + 5 + 1, 1 + 1, true));
+    }
+
+    #[test]
+    fn phases_end_once_every_player_has_finished() {
+        let state = choose(&choose(&two_players(1), 0, Action::Develop), 1, Action::Settle);
+        assert!(state.end_phase().is_err());
+
+        let state = state.apply(Move::Develop { player: 0, placement: None }).unwrap();
+        assert!(state.end_phase().is_err());
+
+        let state = state.apply(Move::Develop { player: 1, placement: None }).unwrap();
+        let (state, _) = state.end_phase().unwrap();
+        assert_eq!(state.step(), Step::Phase(Phase::Settle));
+        assert!(state.end_round().is_err());
+    }
+
+    #[test]
+    fn bonuses_come_from_chosen_actions() {
+        let state = choose(&choose(&two_players(3), 0, Action::Develop), 1, Action::Settle);
+        assert_eq!(state.phase_actions(0), vec![Action::Develop]);
+        assert_eq!(state.phase_actions(1), vec![]);
+
+        // Three cards cost 3 to develop, or 2 with the Develop bonus.
+        let placement = player::Placement { index: 0, payment: vec![1, 2] };
+        let state = state.apply(Move::Develop { player: 0, placement: Some(placement.clone()) }).unwrap();
+        assert_eq!(state.player(0).tableau().len(), 1);
+        assert!(is_illegal(state.apply(Move::Develop { player: 1, placement: Some(placement) })));
+    }
+
+    #[test]
+    fn successful_takeovers_move_the_world() {
+        let state = settling(2);
+        let card = rebel_world();
+        let state = state.apply(Move::Takeover { player: 0, target: 1, card: card.clone() }).unwrap();
+
+        assert!(state.player(0).tableau().contains(&card));
+        assert!(!state.player(1).tableau().contains(&card));
+        assert!(state.has_finished_phase(0));
+    }
+
+    #[test]
+    fn failed_takeovers_still_end_the_settle_phase() {
+        let state = settling(1);
+        let card = rebel_world();
+        let state = state.apply(Move::Takeover { player: 0, target: 1, card: card.clone() }).unwrap();
+
+        assert!(!state.player(0).tableau().contains(&card));
+        assert!(state.player(1).tableau().contains(&card));
+        assert!(state.has_finished_phase(0));
+    }
+
+    #[test]
+    fn only_takeover_targets_can_be_attacked() {
+        let state = settling(5);
+        let loyal = test_cards::military_world("Loyal World", 2);
+        assert!(is_illegal(state.apply(Move::Takeover { player: 0, target: 1, card: loyal })));
+
+        let rebel = rebel_world();
+        assert!(is_illegal(state.apply(Move::Takeover { player: 1, target: 0, card: rebel.clone() })));
+        assert!(is_illegal(state.apply(Move::Takeover { player: 0, target: 0, card: rebel })));
+    }
+
+    #[test]
+    fn imperium_cards_take_over_rebel_worlds() {
+        let deck = cards::get_deck(&[cards::Expansion::RebelVsImperium]);
+        let find = |name: &str| { deck.iter().find(|card| { card.name == name }).unwrap().clone() };
+        let fleet = find("Imperium Invasion Fleet");
+        let miners = find("Rebel Miners");
+
+        let mut state = two_players(1);
+        state.players[0].gain_world(fleet);
+        state.players[1].gain_world(miners.clone());
+        let state = choose(&choose(&state, 0, Action::Settle), 1, Action::Settle);
+
+        // The fleet's +2 military matches the Rebel Miners' defense of 2.
+        let options = state.takeover_options(0);
+        assert_eq!(options.len(), 1);
+        assert!(options[0].succeeds());
+        assert!(state.takeover_options(1).is_empty());
+
+        let state = state.apply(Move::Takeover { player: 0, target: 1, card: miners.clone() }).unwrap();
+        assert!(state.player(0).tableau().contains(&miners));
+    }
 }
@@ -0,0 +1,305 @@
+use std::fmt;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+use crate::cards;
+use crate::player;
+use crate::score;
+use crate::utils::{self, Variants};
+
+/// The goal tiles from Gathering Storm. "First" goals go to the first
+/// players to reach them, "most" goals to whoever is ahead.
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+pub enum Goal {
+    /// First to have 8 cards in their tableau.
+    ExpansionLeader,
+    /// First to place a development that costs 6.
+    GalacticStatus,
+    /// First to have 3 Alien cards in their tableau.
+    OverlordDiscoveries,
+    /// First to have a world of every kind of good.
+    SystemDiversity,
+    /// First to have 5 VP chips.
+    GalacticStandardOfLiving,
+
+    /// Most military, at least 6.
+    GreatestMilitary,
+    /// Most developments, at least 4.
+    GreatestInfrastructure,
+    /// Most production worlds, at least 4. Windfall worlds don't count.
+    ProductionLeader,
+    /// Most cards with explore powers, at least 3.
+    ResearchLeader,
+}
+
+impl Goal {
+    pub fn is_first(&self) -> bool {
+        matches!(*self,
+                 Goal::ExpansionLeader |
+                 Goal::GalacticStatus |
+                 Goal::OverlordDiscoveries |
+                 Goal::SystemDiversity |
+                 Goal::GalacticStandardOfLiving)
+    }
+
+    pub fn points(&self) -> i32 {
+        if self.is_first() { 3 } else { 5 }
+    }
+
+    /// The least progress that qualifies for the goal.
+    pub fn minimum(&self) -> i32 {
+        match *self {
+            Goal::ExpansionLeader => 8,
+            Goal::GalacticStatus => 1,
+            Goal::OverlordDiscoveries => 3,
+            Goal::SystemDiversity => cards::Good::variants().len() as i32,
+            Goal::GalacticStandardOfLiving => 5,
+            Goal::GreatestMilitary => 6,
+            Goal::GreatestInfrastructure => 4,
+            Goal::ProductionLeader => 4,
+            Goal::ResearchLeader => 3,
+        }
+    }
+
+    /// How far a player is towards the goal.
+    pub fn progress(&self, player: &player::Player) -> i32 {
+        let tableau = player.tableau();
+        let count = |f: &dyn Fn(&cards::Card) -> bool| { tableau.iter().filter(|card| { f(card) }).count() as i32 };
+
+        match *self {
+            Goal::ExpansionLeader => tableau.len() as i32,
+            Goal::GalacticStatus => count(&|card| {
+                card.card_type == cards::CardType::Development && card.cost == cards::Cost::Trade(6)
+            }),
+            Goal::OverlordDiscoveries => count(&|card| { card.attributes.contains(&cards::Attribute::Alien) }),
+            Goal::SystemDiversity => {
+                cards::Good::variants().into_iter()
+                    .filter(|good| { tableau.iter().any(|card| { score::matches(&cards::Condition::Good(good.clone()), card) }) })
+                    .count() as i32
+            },
+            Goal::GalacticStandardOfLiving => player.vp_chips(),
+            Goal::GreatestMilitary => player.military(),
+            Goal::GreatestInfrastructure => count(&|card| { card.card_type == cards::CardType::Development }),
+            Goal::ProductionLeader => count(&|card| {
+                card.produces.as_ref().is_some_and(|(production, _)| { *production == cards::Production::Produces })
+            }),
+            Goal::ResearchLeader => count(&|card| {
+                card.powers.iter().any(|p| { p.power_type() == Some(cards::PowerType::Explore) })
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let description = match *self {
+            Goal::ExpansionLeader => "Expansion Leader: first to have 8 cards in their tableau",
+            Goal::GalacticStatus => "Galactic Status: first to place a 6 cost development",
+            Goal::OverlordDiscoveries => "Overlord Discoveries: first to have 3 Alien cards",
+            Goal::SystemDiversity => "System Diversity: first to have a world of every kind of good",
+            Goal::GalacticStandardOfLiving => "Galactic Standard of Living: first to have 5 VP chips",
+            Goal::GreatestMilitary => "Greatest Military: most military, at least 6",
+            Goal::GreatestInfrastructure => "Greatest Infrastructure: most developments, at least 4",
+            Goal::ProductionLeader => "Production Leader: most production worlds, at least 4",
+            Goal::ResearchLeader => "Research Leader: most cards with explore powers, at least 3",
+        };
+        write!(fmt, "{} ({} VP)", description, self.points())
+    }
+}
+
+impl utils::Variants for Goal {
+    fn variants() -> Vec<Goal> {
+        vec![
+            Goal::ExpansionLeader,
+            Goal::GalacticStatus,
+            Goal::OverlordDiscoveries,
+            Goal::SystemDiversity,
+            Goal::GalacticStandardOfLiving,
+            Goal::GreatestMilitary,
+            Goal::GreatestInfrastructure,
+            Goal::ProductionLeader,
+            Goal::ResearchLeader,
+        ]
+    }
+}
+
+/// A goal in play and the players, as indices, that hold it. Several players
+/// can hold a first goal, but a most goal has at most one holder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalTile {
+    pub goal: Goal,
+    pub holders: Vec<usize>,
+}
+
+/// A change in who holds a goal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Claim {
+    Claimed { goal: Goal, player: usize },
+    Lost { goal: Goal, player: usize },
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Claim::Claimed { ref goal, player } => write!(fmt, "Player {} claims {}.", player + 1, goal),
+            Claim::Lost { ref goal, player } => write!(fmt, "Player {} loses {}.", player + 1, goal),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goals {
+    tiles: Vec<GoalTile>,
+}
+
+impl Goals {
+    pub fn new(goals: Vec<Goal>) -> Goals {
+        Goals { tiles: goals.into_iter().map(|goal| { GoalTile { goal, holders: vec![] } }).collect() }
+    }
+
+    /// Draw the goals for a game at random: four first goals and two most
+    /// goals.
+    pub fn random() -> Goals {
+        let mut rng = thread_rng();
+        let (first, most): (Vec<Goal>, Vec<Goal>) = Goal::variants().into_iter().partition(|g| { g.is_first() });
+
+        let mut goals: Vec<Goal> = first.choose_multiple(&mut rng, 4).cloned().collect();
+        goals.extend(most.choose_multiple(&mut rng, 2).cloned());
+        Goals::new(goals)
+    }
+
+    pub fn tiles(&self) -> &[GoalTile] {
+        &self.tiles
+    }
+
+    /// The victory points from the goals a player holds.
+    pub fn points(&self, player: usize) -> i32 {
+        self.tiles.iter()
+            .filter(|tile| { tile.holders.contains(&player) })
+            .map(|tile| { tile.goal.points() })
+            .sum()
+    }
+
+    /// Hand out goals at the end of a phase.
+    ///
+    /// Every player who reaches an unclaimed first goal in the same phase
+    /// gets it. A most goal goes to the one player who is strictly ahead and
+    /// at least at the minimum. Its holder keeps it on a tie, and loses it
+    /// when someone passes them or they fall below the minimum. If the
+    /// leaders are tied and none of them holds it, nobody does.
+    pub fn evaluate(&mut self, players: &[player::Player]) -> Vec<Claim> {
+        let mut claims = vec![];
+
+        for tile in self.tiles.iter_mut() {
+            let progress: Vec<i32> = players.iter().map(|p| { tile.goal.progress(p) }).collect();
+            let minimum = tile.goal.minimum();
+
+            if tile.goal.is_first() {
+                if tile.holders.is_empty() {
+                    tile.holders = (0..players.len()).filter(|&i| { progress[i] >= minimum }).collect();
+                    for &player in tile.holders.iter() {
+                        claims.push(Claim::Claimed { goal: tile.goal.clone(), player });
+                    }
+                }
+                continue;
+            }
+
+            let best = progress.iter().cloned().max().unwrap_or(0);
+            let leaders: Vec<usize> = (0..players.len()).filter(|&i| { progress[i] == best }).collect();
+            let holder = tile.holders.first().cloned();
+
+            let new_holder = if best < minimum {
+                None
+            } else if holder.is_some_and(|h| { leaders.contains(&h) }) {
+                holder
+            } else if leaders.len() == 1 {
+                Some(leaders[0])
+            } else {
+                None
+            };
+
+            if new_holder != holder {
+                if let Some(player) = holder {
+                    claims.push(Claim::Lost { goal: tile.goal.clone(), player });
+                }
+                if let Some(player) = new_holder {
+                    claims.push(Claim::Claimed { goal: tile.goal.clone(), player });
+                }
+                tile.holders = new_holder.into_iter().collect();
+            }
+        }
+
+        claims
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{self, test_cards};
+    use crate::player::Player;
+    use super::{Claim, Goal, Goals};
+
+    fn with_developments(count: usize) -> Player {
+        let mut player = Player::new();
+        for card in test_cards::developments(count, 1) {
+            player.gain_world(card);
+        }
+        player
+    }
+
+    #[test]
+    fn production_leader_ignores_windfall_worlds() {
+        let world = |production| {
+            cards::Card { produces: Some((production, cards::Good::Novelty)), ..test_cards::world("World", 1) }
+        };
+        let mut player = Player::new();
+        for _ in 0..3 {
+            player.gain_world(world(cards::Production::Produces));
+        }
+        player.gain_world(world(cards::Production::Windfall));
+        assert_eq!(Goal::ProductionLeader.progress(&player), 3);
+    }
+
+    #[test]
+    fn holder_keeps_a_tied_goal() {
+        let mut goals = Goals::new(vec![Goal::GreatestInfrastructure]);
+        let claims = goals.evaluate(&[with_developments(4), with_developments(3)]);
+        assert_eq!(claims, vec![Claim::Claimed { goal: Goal::GreatestInfrastructure, player: 0 }]);
+
+        assert!(goals.evaluate(&[with_developments(4), with_developments(4)]).is_empty());
+        assert_eq!(goals.tiles()[0].holders, vec![0]);
+
+        let claims = goals.evaluate(&[with_developments(4), with_developments(5)]);
+        assert_eq!(claims, vec![
+            Claim::Lost { goal: Goal::GreatestInfrastructure, player: 0 },
+            Claim::Claimed { goal: Goal::GreatestInfrastructure, player: 1 },
+        ]);
+    }
+
+    #[test]
+    fn nobody_holds_a_goal_while_the_leaders_tie() {
+        let mut goals = Goals::new(vec![Goal::GreatestInfrastructure]);
+        assert!(goals.evaluate(&[with_developments(4), with_developments(4), with_developments(2)]).is_empty());
+        assert!(goals.tiles()[0].holders.is_empty());
+
+        let claims = goals.evaluate(&[with_developments(4), with_developments(5), with_developments(2)]);
+        assert_eq!(claims, vec![Claim::Claimed { goal: Goal::GreatestInfrastructure, player: 1 }]);
+    }
+
+    #[test]
+    fn first_goals_are_shared_when_reached_together() {
+        let mut goals = Goals::new(vec![Goal::ExpansionLeader]);
+        assert!(goals.evaluate(&[with_developments(7), with_developments(7)]).is_empty());
+
+        let claims = goals.evaluate(&[with_developments(8), with_developments(8), with_developments(7)]);
+        assert_eq!(claims, vec![
+            Claim::Claimed { goal: Goal::ExpansionLeader, player: 0 },
+            Claim::Claimed { goal: Goal::ExpansionLeader, player: 1 },
+        ]);
+        assert_eq!(goals.points(0), 3);
+        assert_eq!(goals.points(1), 3);
+
+        assert!(goals.evaluate(&[with_developments(8), with_developments(8), with_developments(8)]).is_empty());
+        assert_eq!(goals.points(2), 0);
+    }
+}
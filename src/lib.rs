@@ -0,0 +1,12 @@
+pub mod advisor;
+pub mod cards;
+pub mod draft;
+pub mod error;
+pub mod game;
+pub mod goals;
+pub mod player;
+pub mod robot;
+pub mod score;
+pub mod search;
+pub mod session;
+pub mod utils;
@@ -1,48 +1,783 @@
-#![feature(core)]
-#![feature(collections)]
-#![feature(io)]
-#![feature(box_syntax)]
+use std::env;
 
-extern crate rand;
+use rftg::{advisor, cards, draft, error, game, goals, player, robot, search, utils};
+use rftg::utils::{Input, Variants};
+use rftg::error::RftgError;
+use rftg::game::{GameState, Move};
+use rftg::session::Session;
 
-use std::cell::RefCell;
+/// How to set up the game, from the command line.
+struct Options {
+    advanced: bool,
+    solo: bool,
+    draft: bool,
+    expansions: Vec<cards::Expansion>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> error::Result<Options> {
+        let mut options = Options { advanced: false, solo: false, draft: false, expansions: vec![] };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--advanced" => options.advanced = true,
+                "--solo" => options.solo = true,
+                "--draft" => options.draft = true,
+                "--expansion" => {
+                    let name = args.next().map(|name| { name.as_str() }).unwrap_or("");
+                    match utils::parse_variant(name) {
+                        Some(expansion) => options.expansions.push(expansion),
+                        None => return Err(RftgError::InvalidData(format!("Unknown expansion \"{}\".", name))),
+                    }
+                },
+                _ => return Err(RftgError::InvalidData(format!("Unknown option \"{}\".", arg))),
+            }
+        }
 
-mod cards;
-mod utils;
-mod game;
-mod player;
+        if options.solo && options.draft {
+            return Err(RftgError::InvalidData("The draft can't be played solo.".to_string()));
+        }
+        let has = |expansion| { options.expansions.contains(&expansion) };
+        if has(cards::Expansion::BrinkOfWar) &&
+            !(has(cards::Expansion::GatheringStorm) && has(cards::Expansion::RebelVsImperium)) {
+            return Err(RftgError::InvalidData(
+                "Brink of War needs the Gathering Storm and Rebel vs Imperium expansions.".to_string()));
+        }
+        Ok(options)
+    }
+}
 
-use utils::Variants;
+/// Make a move, reporting any reshuffle it caused.
+fn apply(session: &mut Session, mv: Move) -> error::Result<()> {
+    let before = session.state().clone();
+    session.apply(mv)?;
+    announce_piles(&before, session.state());
+    Ok(())
+}
+
+/// Take back `player`'s last move, if nothing has been revealed since.
+fn undo(session: &mut Session, player: usize) {
+    if session.undo(player) {
+        println!("Undone.");
+    } else {
+        println!("There is nothing to undo.");
+    }
+}
+
+/// Whether it is still `player`'s turn in `phase`, which it isn't once their
+/// last move has finished the phase for them.
+fn is_turn(state: &GameState, player: usize, phase: game::Phase) -> bool {
+    state.step() == game::Step::Phase(phase) && !state.has_finished_phase(player)
+}
+
+/// Every draw and discard pile: the shared one, or each player's own after a
+/// draft.
+fn piles(state: &GameState) -> Vec<&game::Game> {
+    if state.is_drafted() {
+        (0..state.players().len()).map(|index| { state.game_for(index) }).collect()
+    } else {
+        vec![state.game()]
+    }
+}
+
+/// Report reshuffles, and draws from an exhausted deck, between two states.
+fn announce_piles(before: &GameState, after: &GameState) {
+    for (before, after) in piles(before).into_iter().zip(piles(after)) {
+        if after.reshuffles() > before.reshuffles() {
+            println!("Shuffle!");
+        }
+        if after.empty_draws() > before.empty_draws() {
+            println!("The deck is exhausted. No cards can be drawn.");
+        }
+    }
+}
 
 fn main() {
-    let cards = cards::get_cards();
-    let game = game::Game::new(cards);
-    let game_ref = &RefCell::new(game);
-    let mut players = vec![];
-    let num_players = 2;
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|arg| { arg.as_str() }) == Some("cards") {
+        match search::Query::parse(&args[1..]) {
+            Ok(query) => print_cards(&query),
+            Err(err) => println!("{}", err),
+        }
+        return;
+    }
+
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        },
+    };
+    let num_players = if options.solo { 1 } else { 2 };
+    let state = if options.draft {
+        draft_decks(cards::get_deck_for(&options.expansions, num_players), num_players)
+            .map(GameState::drafted)
+    } else {
+        GameState::new(num_players, &options.expansions)
+    };
+    let state = match state {
+        Ok(state) => state,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        },
+    };
+    let mut state = state
+        .advanced(options.advanced)
+        .prestige(options.expansions.contains(&cards::Expansion::BrinkOfWar));
+    if options.solo {
+        state = state.robot(robot::Robot::new());
+    }
+    if options.expansions.contains(&cards::Expansion::GatheringStorm) {
+        state = state.goals(goals::Goals::random());
+    }
+    let mut session = Session::new(state);
+    println!("Type \"undo\" at any prompt to take back your last decision, \"view\" to see the other players,");
+    println!("\"deck\" to count cards, \"odds [query]\" to guess at their hands, or \"card <name>\" to look up a card.");
+    println!();
+
+    loop {
+        let saved = session.clone();
+        let step = session.state().step();
+        let result = match step {
+            game::Step::ChooseActions => choose_actions(&mut session),
+            game::Step::Phase(ref phase) => play_phase(&mut session, phase),
+            game::Step::EndOfRound => end_round(&mut session),
+        };
+
+        match result {
+            Ok(()) => {
+                if step != game::Step::EndOfRound || session.state().step() != game::Step::ChooseActions {
+                    continue;
+                }
+                if session.state().is_over() {
+                    print_final_scores(session.state());
+                    return;
+                }
+            },
+            Err(RftgError::Io(err)) => {
+                println!("Could not read input: {}", err);
+                return;
+            },
+            Err(err) => {
+                println!("{}", err);
+                session = saved;
+            },
+        }
+
+        println!();
+    }
+}
+
+/// Draft a deck for each player, with the computer picking for everyone
+/// but the first player.
+fn draft_decks(deck: Vec<cards::Card>, num_players: usize) -> error::Result<Vec<Vec<cards::Card>>> {
+    let mut draft = draft::Draft::new(deck, num_players, draft::PACK_SIZE);
+
+    while !draft.is_done() {
+        println!("You have drafted {} cards. Choose a card to draft.", draft.picked(0).len());
+        let pick = match utils::select_index(draft.pack(0)) {
+            Ok(Input::Undo) => {
+                println!("There is nothing to undo.");
+                continue;
+            },
+            Ok(Input::Command(command)) => {
+                match command.strip_prefix("card ") {
+                    Some(name) => print_cards(&search::Query::new().name(name.trim())),
+                    None => println!("Only \"card <name>\" can be used during the draft."),
+                }
+                continue;
+            },
+            Ok(Input::Answer(pick)) => pick,
+            Err(err) => return Err(err),
+        };
+
+        let mut picks = vec![pick];
+        for player in 1..num_players {
+            picks.push(draft::ai_pick(draft.pack(player)));
+        }
+        draft.pick(&picks)?;
+    }
+
+    println!();
+    Ok(draft.into_decks())
+}
+
+/// In a game with more than one player, say whose turn it is, since they all
+/// share the terminal.
+fn announce_player(state: &GameState, player: usize) {
+    if state.players().len() > 1 {
+        println!("Player {}:", player + 1);
+    }
+}
+
+/// Ask the next player who hasn't yet for this round's action cards. Once
+/// everyone has, say what the robot rolled.
+fn choose_actions(session: &mut Session) -> error::Result<()> {
+    let state = session.state();
+    let player = match (0..state.players().len()).find(|&player| { state.chosen_actions(player).is_none() }) {
+        Some(player) => player,
+        None => return Ok(()),
+    };
+    announce_player(state, player);
+    state.player(player).print_hand();
+    state.player(player).print_tableau();
+
+    let actions = choose_player_actions(state, player)?;
+    apply(session, Move::ChooseActions { player, actions })?;
+    println!();
+
+    if let Some(action) = session.state().robot_action() {
+        println!("The robot chooses {}.", action);
+    }
+    Ok(())
+}
+
+/// Ask for one player's action cards. Undo goes back to the previous card.
+fn choose_player_actions(state: &GameState, player: usize) -> error::Result<Vec<game::Action>> {
+    let mut chosen = vec![];
+
+    loop {
+        let options = state.action_options(player, &chosen);
+        if options.is_empty() {
+            return Ok(chosen);
+        }
+
+        if chosen.is_empty() {
+            println!("What action would you like to take?");
+        } else {
+            println!("What is your next action?");
+        }
+        match utils::select(&options) {
+            Ok(Input::Undo) if !chosen.is_empty() => { chosen.pop(); },
+            Ok(Input::Undo) => println!("There is nothing to undo."),
+            Ok(Input::Command(command)) => run_command(state, player, &command),
+            Err(err) => return Err(err),
+            Ok(Input::Answer(action)) => chosen.push(action),
+        }
+    }
+}
+
+/// Play the next turn in a phase. Once every player has taken theirs, the
+/// robot plays and goals are handed out.
+fn play_phase(session: &mut Session, phase: &game::Phase) -> error::Result<()> {
+    let state = session.state();
+    let player = match (0..state.players().len()).find(|&player| { !state.has_finished_phase(player) }) {
+        Some(player) => player,
+        None => {
+            let before = session.state().clone();
+            if let Some(turn) = session.robot_phase()? {
+                announce_piles(&before, session.state());
+                println!("{}", turn);
+            }
+            for claim in session.end_phase()? {
+                println!("{}", claim);
+            }
+            return Ok(());
+        },
+    };
+    announce_player(state, player);
+
+    let searching = state.phase_actions(player).contains(&game::Action::Search);
+    match *phase {
+        game::Phase::Explore if searching => search(session, player),
+        game::Phase::Explore => explore(session, player),
+        game::Phase::Develop => develop(session, player),
+        game::Phase::Settle => settle(session, player),
+    }
+}
+
+fn explore(session: &mut Session, player: usize) -> error::Result<()> {
+    let mut declined_discard = false;
+
+    loop {
+        if !is_turn(session.state(), player, game::Phase::Explore) {
+            return Ok(());
+        }
+        let actions = session.state().phase_actions(player);
+        let p = session.state().player(player);
+        let counts = p.explore_counts(&actions);
+
+        if !p.has_explored() {
+            if counts.discard_then_see > 0 && !p.hand().is_empty() && !declined_discard {
+                println!("Discard a card from your hand to explore one more card?");
+                match utils::select_optional_index(p.hand()) {
+                    Ok(Input::Undo) => undo(session, player),
+                    Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+                    Err(err) => return Err(err),
+                    Ok(Input::Answer(None)) => declined_discard = true,
+                    Ok(Input::Answer(Some(index))) => apply(session, Move::ExploreDiscard { player, index })?,
+                }
+                continue;
+            }
+
+            apply(session, Move::Explore { player })?;
+            continue;
+        }
+
+        let mv = if counts.mix {
+            println!("You explored:");
+            for card in p.explored().iter() {
+                println!("    {}", card);
+            }
+
+            let discard = if p.mix_discards() > 0 {
+                println!("Choose {} cards to discard from your hand.", p.mix_discards());
+                utils::select_many_indices(p.hand(), p.mix_discards())
+            } else {
+                Ok(Input::Answer(vec![]))
+            };
+            discard.map(|input| { input.map(|discard| { Move::ExploreMixDiscard { player, discard } }) })
+        } else {
+            println!("Choose cards to keep.");
+            utils::select_many_indices(p.explored(), p.explored_to_keep())
+                .map(|input| { input.map(|keep| { Move::ExploreKeep { player, keep } }) })
+        };
+
+        match mv {
+            Ok(Input::Undo) => undo(session, player),
+            Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+            Err(err) => return Err(err),
+            Ok(Input::Answer(mv)) => return apply(session, mv),
+        }
+    }
+}
+
+fn search(session: &mut Session, player: usize) -> error::Result<()> {
+    loop {
+        if !is_turn(session.state(), player, game::Phase::Explore) {
+            return Ok(());
+        }
+        let p = session.state().player(player);
+
+        if !p.has_searched() {
+            println!("What would you like to search for?");
+            match utils::select(&cards::SearchCategory::variants()) {
+                Ok(Input::Undo) => undo(session, player),
+                Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+                Err(err) => return Err(err),
+                Ok(Input::Answer(category)) => apply(session, Move::Search { player, category })?,
+            }
+            continue;
+        }
+
+        let found = match p.search_found() {
+            Some(found) => found,
+            None => {
+                println!("No card matches.");
+                return Ok(());
+            },
+        };
 
-    for _ in 0..num_players {
-        let mut pa = player::Player::new(game_ref);
-        pa.draw_up_to(3);
+        println!("You found {}", found);
+        match utils::select_index(&["Keep it", "Discard it and take the next match"]) {
+            Ok(Input::Undo) => undo(session, player),
+            Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+            Err(err) => return Err(err),
+            Ok(Input::Answer(choice)) => return apply(session, Move::SearchKeep { player, keep: choice == 0 }),
+        }
+    }
+}
+
+fn develop(session: &mut Session, player: usize) -> error::Result<()> {
+    loop {
+        if !is_turn(session.state(), player, game::Phase::Develop) {
+            return Ok(());
+        }
+        let actions = session.state().phase_actions(player);
+        let p = session.state().player(player);
+
+        if !p.has_drawn_for_develop() {
+            let hand_before = p.hand().len();
+            apply(session, Move::DevelopDraw { player })?;
+
+            let hand = session.state().player(player).hand();
+            if hand.len() > hand_before {
+                println!("Drawing {} cards.", hand.len() - hand_before);
+                for card in hand[hand_before..].iter() {
+                    println!("    {}", card);
+                }
+            }
+            continue;
+        }
+
+        println!("You have an effective trade power of {}", p.develop_trade_power());
+
+        let options = p.develop_options(actions.first());
+        if options.is_empty() {
+            println!("You can't afford any developments.");
+            return apply(session, Move::Develop { player, placement: None });
+        }
+
+        println!("What would you like to develop?");
+        println!();
 
-        players.push(pa);
+        let option = match utils::select_optional(&options) {
+            Ok(Input::Undo) => {
+                undo(session, player);
+                continue;
+            },
+            Ok(Input::Command(command)) => {
+                run_command(session.state(), player, &command);
+                continue;
+            },
+            Err(err) => return Err(err),
+            Ok(Input::Answer(None)) => return apply(session, Move::Develop { player, placement: None }),
+            Ok(Input::Answer(Some(option))) => option,
+        };
+
+        // Undoing the payment, or running a command, goes back to choosing
+        // the development.
+        let payment = match choose_payment(p, option.index, option.price) {
+            Ok(Input::Undo) => continue,
+            Ok(Input::Command(command)) => {
+                run_command(session.state(), player, &command);
+                continue;
+            },
+            Ok(Input::Answer(payment)) => payment,
+            Err(err) => return Err(err),
+        };
+
+        let placement = Some(player::Placement { index: option.index, payment });
+        return apply(session, Move::Develop { player, placement });
     }
+}
 
-    let phases = game::Phase::variants();
+fn settle(session: &mut Session, player: usize) -> error::Result<()> {
+    let mut declined_discard = false;
+    let mut declined_takeover = false;
 
     loop {
-        players[0].print_hand();
-        players[0].print_tableau();
+        if !is_turn(session.state(), player, game::Phase::Settle) {
+            return Ok(());
+        }
+        let p = session.state().player(player);
+
+        let discards = p.military_discard_options();
+        if !discards.is_empty() && !declined_discard {
+            println!("Would you like to discard a card for extra military this phase?");
+            match utils::select_optional(&discards) {
+                Ok(Input::Undo) => undo(session, player),
+                Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+                Err(err) => return Err(err),
+                Ok(Input::Answer(None)) => declined_discard = true,
+                Ok(Input::Answer(Some(discard))) => apply(session, Move::MilitaryDiscard { player, discard })?,
+            }
+            continue;
+        }
+
+        let takeovers = session.state().takeover_options(player);
+        if !takeovers.is_empty() && !declined_takeover {
+            println!("Would you like to attempt a takeover instead of settling?");
+            match utils::select_optional(&takeovers) {
+                Ok(Input::Undo) => {
+                    if declined_discard {
+                        declined_discard = false;
+                    } else {
+                        undo(session, player);
+                    }
+                },
+                Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+                Err(err) => return Err(err),
+                Ok(Input::Answer(None)) => declined_takeover = true,
+                Ok(Input::Answer(Some(takeover))) => {
+                    if takeover.succeeds() {
+                        println!("You take over {}.", takeover.card.name);
+                    } else {
+                        println!("Your takeover of {} fails.", takeover.card.name);
+                    }
+                    let game::TakeoverOption { target, card, .. } = takeover;
+                    return apply(session, Move::Takeover { player, target, card });
+                },
+            }
+            continue;
+        }
+
+        p.print_settle_powers();
+
+        let options = p.settle_options();
+        if options.is_empty() {
+            println!("You can't settle any of your worlds.");
+            return apply(session, Move::Settle { player, choice: None });
+        }
+
+        println!("What would you like to settle?");
+        println!();
 
-        println!("What phase would you like to do?");
-        let phase = utils::select(&phases);
+        let option = match utils::select_optional(&options) {
+            Ok(Input::Undo) => {
+                if declined_takeover {
+                    declined_takeover = false;
+                } else if declined_discard {
+                    declined_discard = false;
+                } else {
+                    undo(session, player);
+                }
+                continue;
+            },
+            Ok(Input::Command(command)) => {
+                run_command(session.state(), player, &command);
+                continue;
+            },
+            Err(err) => return Err(err),
+            Ok(Input::Answer(None)) => return apply(session, Move::Settle { player, choice: None }),
+            Ok(Input::Answer(Some(option))) => option,
+        };
+
+        // Undoing the payment, or running a command, goes back to choosing
+        // the world.
+        let payment = match choose_payment(p, option.index, option.method.price()) {
+            Ok(Input::Undo) => continue,
+            Ok(Input::Command(command)) => {
+                run_command(session.state(), player, &command);
+                continue;
+            },
+            Ok(Input::Answer(payment)) => payment,
+            Err(err) => return Err(err),
+        };
+
+        match option.method {
+            player::SettleMethod::Free => println!("You settle the world for free."),
+            player::SettleMethod::Military => println!("Your military conquers the world."),
+            player::SettleMethod::DiscardForFree(ref discard) =>
+                println!("You discard {} and settle the world for free.", discard.name),
+            _ => {},
+        }
+
+        let placement = player::Placement { index: option.index, payment };
+        return apply(session, Move::Settle { player, choice: Some((placement, option.method)) });
+    }
+}
+
+/// Run a command typed at a prompt by `player`. The prompt is asked again
+/// afterwards.
+fn run_command(state: &GameState, player: usize, command: &str) {
+    let mut words = command.splitn(2, ' ');
+    match (words.next(), words.next()) {
+        (Some("view"), None) => view(state, player),
+        (Some("deck"), None) => deck(state),
+        (Some("odds"), args) => odds(state, player, args.unwrap_or("")),
+        (Some("card"), Some(name)) => print_cards(&search::Query::new().name(name.trim())),
+        _ => println!("Unknown command \"{}\". Commands are: undo, view, deck, odds [query], card <name>.", command),
+    }
+    println!();
+}
+
+/// Show what card counting can tell about the draw and discard piles.
+fn deck(state: &GameState) {
+    if state.is_drafted() {
+        for index in 0..state.players().len() {
+            println!("Player {}'s piles:", index + 1);
+            print_deck_stats(&state.game_for(index).stats());
+        }
+    } else {
+        print_deck_stats(&state.game().stats());
+    }
+}
+
+fn print_deck_stats(stats: &game::DeckStats) {
+    println!("{} cards left to draw, {} in the discard pile, {} reshuffles so far.",
+             stats.draw_pile, stats.discard_pile, stats.reshuffles);
+    if stats.public_discards == 0 {
+        println!("None of the discarded cards are known.");
+        return;
+    }
+
+    let types: Vec<String> = stats.by_type.iter().map(|(t, n)| { format!("{:?} {}", t, n) }).collect();
+    let goods: Vec<String> = stats.by_good.iter().map(|(g, n)| { format!("{:?} {}", g, n) }).collect();
+    let costs: Vec<String> = stats.by_cost.iter()
+        .map(|(cost, n)| {
+            match *cost {
+                cards::Cost::Free => format!("free {}", n),
+                cards::Cost::Trade(c) => format!("{} trade {}", c, n),
+                cards::Cost::Military(c) => format!("{} military {}", c, n),
+            }
+        })
+        .collect();
+
+    println!("{} of the discarded cards are known:", stats.public_discards);
+    println!("    Types: {}", types.join(", "));
+    println!("    Goods: {}", goods.join(", "));
+    println!("    Costs: {}", costs.join(", "));
+}
+
+/// Estimate what the other players are holding, from what `player` has
+/// seen: the chance of a card that matches the query, which takes the same
+/// arguments as `rftg cards`, or of worlds and developments without one.
+fn odds(state: &GameState, player: usize, args: &str) {
+    let advisor = match advisor::Advisor::new(state, player) {
+        Ok(advisor) => advisor,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        },
+    };
+
+    let args: Vec<String> = args.split_whitespace().map(|s| { s.to_string() }).collect();
+    let queries = if args.is_empty() {
+        vec![
+            ("a world".to_string(), search::Query::new().card_type(cards::CardType::World)),
+            ("a development".to_string(), search::Query::new().card_type(cards::CardType::Development)),
+        ]
+    } else {
+        match search::Query::parse(&args) {
+            Ok(query) => vec![("a matching card".to_string(), query)],
+            Err(err) => {
+                println!("{}", err);
+                return;
+            },
+        }
+    };
+
+    if state.players().len() < 2 {
+        println!("There are no other players with hands.");
+        return;
+    }
+
+    println!("You haven't seen {} cards.", advisor.unseen().len());
+    for index in (0..state.players().len()).filter(|&index| { index != player }) {
+        let p = state.player(index);
+        println!("Player {} holds {} cards and has seen at least {}.",
+                 index + 1, p.hand().len(), advisor.seen_by(index));
+        for (description, query) in queries.iter() {
+            println!("    {:.0}% chance of {}", 100.0 * advisor.chance(index, query), description);
+        }
+    }
+}
+
+/// Show everything that is public: the piles and the tableau of everyone but
+/// `player`.
+fn view(state: &GameState, player: usize) {
+    if state.is_drafted() {
+        for index in 0..state.players().len() {
+            let game = state.game_for(index);
+            println!("Player {}'s draw pile has {} cards and their discard pile has {}.",
+                     index + 1, game.draw_pile_len(), game.discard_pile_len());
+        }
+    } else {
+        println!("The draw pile has {} cards and the discard pile has {}.",
+                 state.game().draw_pile_len(), state.game().discard_pile_len());
+    }
+    println!("There are {} VP chips left.", state.game().vp_chips_left());
+    println!();
+
+    for (index, p) in state.players().iter().enumerate().filter(|&(index, _)| { index != player }) {
+        p.print_public(&format!("Player {}", index + 1));
+    }
+
+    if let Some(robot) = state.get_robot() {
+        println!("The robot: {} VP, {} credits, {} military.", robot.score(), robot.credits(), robot.military());
+        for card in robot.tableau().iter() {
+            println!("    {}", card);
+        }
+        println!();
+    }
+
+    if !state.goal_tiles().is_empty() {
+        println!("Goals:");
+        for tile in state.goal_tiles().iter() {
+            let holders: Vec<String> = tile.holders.iter().map(|&i| { format!("player {}", i + 1) }).collect();
+            if holders.is_empty() {
+                println!("    {}", tile.goal);
+            } else {
+                println!("    {}, held by {}", tile.goal, holders.join(" and "));
+            }
+        }
+        for index in 0..state.players().len() {
+            println!("Player {} has {} VP including goals.", index + 1, state.score(index));
+        }
+        println!();
+    }
+}
+
+fn print_final_scores(state: &GameState) {
+    println!("The game is over.");
+    for index in 0..state.players().len() {
+        println!("Player {} scores {} VP.", index + 1, state.score(index));
+    }
+    if let Some(robot) = state.get_robot() {
+        println!("The robot scores {} VP.", robot.score());
+        if robot.score() >= state.score(0) {
+            println!("The robot wins.");
+        } else {
+            println!("You win!");
+        }
+    }
+}
+
+fn print_cards(query: &search::Query) {
+    let entries = query.search(&cards::get_deck(&cards::Expansion::variants()));
+    if entries.is_empty() {
+        println!("No cards match.");
+    }
+    for entry in entries.iter() {
+        entry.print();
+    }
+}
+
+/// Ask which cards to pay with, as indices into the hand, never offering the
+/// card being placed. Then ask to confirm, since once the card is placed the
+/// robot or the next player may reveal cards and the payment can't be undone.
+fn choose_payment(p: &player::Player, placed: usize, price: i32) -> error::Result<Input<Vec<usize>>> {
+    let payment = if price <= 0 {
+        println!("Your cost is 0.");
+        vec![]
+    } else {
+        let others: Vec<usize> = (0..p.hand().len()).filter(|&i| { i != placed }).collect();
+        let other_cards: Vec<cards::Card> = others.iter().map(|&i| { p.hand()[i].clone() }).collect();
+
+        println!("Choose cards to use as payment.");
+        match utils::select_many_indices(&other_cards, price as usize)? {
+            Input::Answer(chosen) => chosen.into_iter().map(|i| { others[i] }).collect(),
+            Input::Undo => return Ok(Input::Undo),
+            Input::Command(command) => return Ok(Input::Command(command)),
+        }
+    };
+
+    let names: Vec<&str> = payment.iter().map(|&i| { p.hand()[i].name.as_str() }).collect();
+    if names.is_empty() {
+        println!("Place {}?", p.hand()[placed].name);
+    } else {
+        println!("Place {}, paying with {}?", p.hand()[placed].name, names.join(", "));
+    }
+    match utils::select_index(&["Yes", "No, choose again"])? {
+        Input::Answer(0) => Ok(Input::Answer(payment)),
+        Input::Answer(_) | Input::Undo => Ok(Input::Undo),
+        Input::Command(command) => Ok(Input::Command(command)),
+    }
+}
+
+/// The clean up at the end of each round: every player over their hand limit
+/// discards down to it.
+fn end_round(session: &mut Session) -> error::Result<()> {
+    loop {
+        if session.state().step() != game::Step::EndOfRound {
+            return Ok(());
+        }
+        let state = session.state();
+        let over_limit = (0..state.players().len())
+            .find(|&player| { state.player(player).hand_limit_excess() > 0 });
 
-        println!("");
-        players[0].act(phase);
+        let player = match over_limit {
+            None => {
+                if let Some(leader) = session.end_round()? {
+                    println!("Player {} leads in prestige and takes a VP chip.", leader + 1);
+                }
+                return Ok(());
+            },
+            Some(player) => player,
+        };
 
-        println!("");
+        let p = state.player(player);
+        let excess = p.hand_limit_excess();
+        println!("Player {}, your hand is over the limit. Choose {} cards to discard.", player + 1, excess);
+        match utils::select_many_indices(p.hand(), excess) {
+            Ok(Input::Undo) => undo(session, player),
+            Ok(Input::Command(command)) => run_command(session.state(), player, &command),
+            Err(err) => return Err(err),
+            Ok(Input::Answer(discard)) => apply(session, Move::HandLimitDiscard { player, discard })?,
+        }
     }
-    //
-    // println!("Player 1, Explore!");
-    // players[0].explore();
 }
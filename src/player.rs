@@ -1,15 +1,21 @@
-extern crate rand;
-
-use std::cell::RefCell;
+use std::{cmp, fmt};
 use std::collections::HashMap;
 
-use cards;
-use game;
-use utils::{self, Variants};
+use crate::cards;
+use crate::error::{self, RftgError};
+use crate::game;
+use crate::score;
+use crate::utils::Variants;
 
 struct Capabilities {
+    hand_size: i32,
+
     explore_to_see: i32,
     explore_to_keep: i32,
+    explore_to_see_if_chosen: i32,
+    explore_to_keep_if_chosen: i32,
+    explore_mix: bool,
+    explore_discard_then_see: i32,
 
     develop_trade_power: i32,
     develop_trade_discount: i32,
@@ -25,6 +31,15 @@ struct Capabilities {
     settle_conversion_discount: i32,
     settle_discard_military: Vec<(cards::Card, i32)>,
     settle_discard_to_negate_trade_if_good: Vec<(cards::Card, Option<cards::Good>)>,
+    settle_hand_discard_military: i32,
+
+    takeover_rebel: bool,
+    takeover_imperium: bool,
+    takeover_defense: i32,
+
+    settle_prestige_if_military: i32,
+
+    hand_limit: i32,
 }
 
 impl Capabilities {
@@ -45,8 +60,14 @@ impl Capabilities {
         }
 
         Capabilities {
+            hand_size,
+
             explore_to_see: 2,
             explore_to_keep: 1,
+            explore_to_see_if_chosen: 0,
+            explore_to_keep_if_chosen: 0,
+            explore_mix: false,
+            explore_discard_then_see: 0,
 
             develop_trade_power: hand_size - 1,
             develop_trade_discount: 0,
@@ -62,34 +83,389 @@ impl Capabilities {
             settle_conversion_discount: 0,
             settle_discard_military: vec![],
             settle_discard_to_negate_trade_if_good: vec![],
+            settle_hand_discard_military: 0,
+
+            takeover_rebel: false,
+            takeover_imperium: false,
+            takeover_defense: 0,
+
+            settle_prestige_if_military: 0,
+
+            hand_limit: 10,
+        }
+    }
+
+    /// The number of cards needed to pay for a development, or `None` if it
+    /// can't be afforded with the rest of the hand.
+    fn develop_price(&self, card: &cards::Card) -> Option<i32> {
+        let cost = match card.cost {
+            cards::Cost::Trade(cost) => cost,
+            cards::Cost::Military(cost) => cost,
+            cards::Cost::Free => 0,
+        };
+        let price = cmp::max(0, cost - self.develop_trade_discount);
+
+        // The development being placed can't be used to pay for itself.
+        if price < self.hand_size {
+            Some(price)
+        } else {
+            None
+        }
+    }
+
+    /// The military strength that can be brought against a particular world.
+    fn military_against(&self, card: &cards::Card) -> i32 {
+        let good = card.produces.clone().map(|(_, good)| good);
+        let mut military = self.settle_military_power.military();
+        military += self.settle_good_military[&good];
+        for attr in card.attributes.iter() {
+            military += self.settle_attr_military[attr];
+        }
+        military
+    }
+
+    /// The trade discount that applies when paying for a particular world.
+    fn settle_discount_for(&self, card: &cards::Card) -> i32 {
+        let good = card.produces.clone().map(|(_, good)| good);
+        self.settle_trade_discount + self.settle_good_discounts[&good]
+    }
+
+    /// Every way that `card` could legally be settled right now.
+    fn settle_methods(&self, card: &cards::Card) -> Vec<SettleMethod> {
+        // The world being placed can't be used to pay for itself.
+        let payment_cards = self.hand_size - 1;
+        let mut methods = vec![];
+
+        match card.cost {
+            cards::Cost::Free => methods.push(SettleMethod::Free),
+            cards::Cost::Trade(cost) => {
+                let price = cmp::max(0, cost - self.settle_discount_for(card));
+                if price <= payment_cards {
+                    methods.push(SettleMethod::Trade(price));
+                }
+
+                if price > 0 {
+                    let produced = card.produces.clone().map(|(_, good)| good);
+                    for (discard, good) in self.settle_discard_to_negate_trade_if_good.iter() {
+                        if good.is_none() || *good == produced {
+                            methods.push(SettleMethod::DiscardForFree(discard.clone()));
+                        }
+                    }
+                }
+            },
+            cards::Cost::Military(defense) => {
+                if self.military_against(card) >= defense {
+                    methods.push(SettleMethod::Military);
+                }
+
+                // Alien military worlds can never be bought with trade.
+                if self.settle_can_convert_military_to_trade &&
+                   !card.attributes.contains(&cards::Attribute::Alien)
+                {
+                    let discount = self.settle_conversion_discount + self.settle_discount_for(card);
+                    let price = cmp::max(0, defense - discount);
+                    if price <= payment_cards {
+                        methods.push(SettleMethod::MilitaryAsTrade(price));
+                    }
+                }
+            },
+        }
+
+        methods
+    }
+}
+
+/// How many cards a player explores and keeps this phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExploreCounts {
+    pub see: usize,
+    pub keep: usize,
+    /// Explored cards join the hand before the player discards.
+    pub mix: bool,
+    /// How many cards may be discarded from hand before exploring, each
+    /// letting the player see one more card.
+    pub discard_then_see: usize,
+}
+
+/// A card in the tableau, or in hand, that can be discarded for extra
+/// military this phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MilitaryDiscard {
+    pub card: cards::Card,
+    pub military: i32,
+    pub from_hand: bool,
+}
+
+impl fmt::Display for MilitaryDiscard {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.from_hand {
+            write!(fmt, "Discard {} from your hand for +{} military", self.card.name, self.military)
+        } else {
+            write!(fmt, "Discard {} for +{} military", self.card.name, self.military)
         }
     }
 }
 
-pub struct Player<'a> {
-    game: &'a RefCell<game::Game>,
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettleMethod {
+    Free,
+    Military,
+    Trade(i32),
+    MilitaryAsTrade(i32),
+    DiscardForFree(cards::Card),
+}
+
+impl SettleMethod {
+    /// The number of cards from hand this method costs.
+    pub fn price(&self) -> i32 {
+        match *self {
+            SettleMethod::Trade(n) => n,
+            SettleMethod::MilitaryAsTrade(n) => n,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for SettleMethod {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SettleMethod::Free => write!(fmt, "For free"),
+            SettleMethod::Military => write!(fmt, "With military"),
+            SettleMethod::Trade(n) => write!(fmt, "Pay {} cards", n),
+            SettleMethod::MilitaryAsTrade(n) => write!(fmt, "Pay {} cards instead of using military", n),
+            SettleMethod::DiscardForFree(ref card) => write!(fmt, "Discard {} from your tableau to place it for free", card.name),
+        }
+    }
+}
+
+/// A development in hand that can be placed. `index` is its position in the
+/// hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevelopOption {
+    pub index: usize,
+    pub card: cards::Card,
+    pub price: i32,
+}
+
+impl fmt::Display for DevelopOption {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{} - pay {} cards", self.card, self.price)
+    }
+}
+
+/// A world in hand that can be placed, and how it would be paid for. `index`
+/// is its position in the hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettleOption {
+    pub index: usize,
+    pub card: cards::Card,
+    pub method: SettleMethod,
+}
+
+impl fmt::Display for SettleOption {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{} - {}", self.card, self.method)
+    }
+}
+
+/// A card from hand to place, and the other cards from hand to pay for it,
+/// all as indices into the hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub index: usize,
+    pub payment: Vec<usize>,
+}
+
+/// Decisions a player has made part way through a phase.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PhaseProgress {
+    explore_discards: usize,
+    has_explored: bool,
+    explored: Vec<cards::Card>,
+    explore_keep: usize,
+    mixing: bool,
+    develop_drawn: bool,
+    military_bonus: i32,
+    hand_military_discards: usize,
+    search_found: Option<cards::Card>,
+    search_category: Option<cards::SearchCategory>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
     hand: Vec<cards::Card>,
     tableau: Vec<cards::Card>,
+    vp_chips: i32,
+    prestige: i32,
+    has_searched: bool,
+    /// The cards this player has discarded face down, by paying, exploring
+    /// or going over the hand limit, each with how many times the deck had
+    /// been reshuffled at the time.
+    face_down_discards: Vec<(usize, cards::Card)>,
+    progress: PhaseProgress,
+}
+
+impl Default for Player {
+    fn default() -> Player {
+        Player::new()
+    }
 }
 
-impl<'a> Player<'a> {
-    pub fn new(game: &'a RefCell<game::Game>) -> Player {
+impl Player {
+    pub fn new() -> Player {
         Player {
-            game: game,
             hand: vec![],
             tableau: vec![],
+            vp_chips: 0,
+            prestige: 0,
+            has_searched: false,
+            face_down_discards: vec![],
+            progress: Default::default(),
         }
     }
 
-    pub fn draw_up_to(&mut self, up_to: usize) {
+    pub fn hand(&self) -> &[cards::Card] {
+        &self.hand
+    }
+
+    pub fn tableau(&self) -> &[cards::Card] {
+        &self.tableau
+    }
+
+    pub fn vp_chips(&self) -> i32 {
+        self.vp_chips
+    }
+
+    /// Prestige is worth a victory point each at the end of the game.
+    pub fn prestige(&self) -> i32 {
+        self.prestige
+    }
+
+    /// Whether this player has used their once per game Search action.
+    pub fn has_searched(&self) -> bool {
+        self.has_searched
+    }
+
+    pub fn face_down_discards(&self) -> usize {
+        self.face_down_discards.len()
+    }
+
+    /// The cards this player discarded face down that are still in the
+    /// discard pile, after it has been reshuffled `reshuffles` times.
+    pub fn discards_in_pile(&self, reshuffles: usize) -> Vec<&cards::Card> {
+        self.face_down_discards.iter()
+            .filter(|&&(shuffled, _)| { shuffled == reshuffles })
+            .map(|(_, card)| { card })
+            .collect()
+    }
+
+    /// The card found by searching this phase, waiting for the player to
+    /// keep it or pass it up.
+    pub fn search_found(&self) -> Option<&cards::Card> {
+        self.progress.search_found.as_ref()
+    }
+
+    /// Cards explored this phase. When mixing, these are already in the hand.
+    pub fn explored(&self) -> &[cards::Card] {
+        &self.progress.explored
+    }
+
+    /// Whether this player has drawn their cards for the explore phase.
+    pub fn has_explored(&self) -> bool {
+        self.progress.has_explored
+    }
+
+    /// How many explored cards may be kept.
+    pub fn explored_to_keep(&self) -> usize {
+        self.progress.explore_keep
+    }
+
+    /// Whether the explored cards have been mixed into the hand, waiting for
+    /// the discard that finishes exploring.
+    pub fn is_mixing(&self) -> bool {
+        self.progress.mixing
+    }
+
+    /// How many cards must be discarded to finish a mixed explore.
+    pub fn mix_discards(&self) -> usize {
+        if self.progress.mixing {
+            self.progress.explored.len() - self.progress.explore_keep
+        } else {
+            0
+        }
+    }
+
+    /// Whether this player has drawn their `DevelopDraw` bonus cards this
+    /// phase.
+    pub fn has_drawn_for_develop(&self) -> bool {
+        self.progress.develop_drawn
+    }
+
+    /// Military gained by discarding cards from the tableau this phase.
+    pub fn military_bonus(&self) -> i32 {
+        self.progress.military_bonus
+    }
+
+    pub fn military(&self) -> i32 {
+        self.get_capabilities().settle_military_power.military()
+    }
+
+    /// The military this player can bring against a world this phase,
+    /// counting bonuses against its good or attributes.
+    pub fn military_against(&self, card: &cards::Card) -> i32 {
+        let mut caps = self.get_capabilities();
+        caps.settle_military_power = caps.settle_military_power + self.progress.military_bonus;
+        caps.military_against(card)
+    }
+
+    /// Whether this player's powers let them attack `card`, a world in
+    /// `owner`'s tableau. Only military worlds can be taken over: Rebel ones
+    /// with `TakeoverRebel`, or any of an Imperium player's with
+    /// `TakeoverImperium`.
+    pub fn can_take_over(&self, owner: &Player, card: &cards::Card) -> bool {
+        let military_world = card.card_type == cards::CardType::World &&
+            matches!(card.cost, cards::Cost::Military(_));
+        if !military_world {
+            return false;
+        }
+
+        let caps = self.get_capabilities();
+        let owner_is_imperium = owner.tableau.iter()
+            .any(|c| { c.attributes.contains(&cards::Attribute::Imperium) });
+        (caps.takeover_rebel && card.attributes.contains(&cards::Attribute::Rebel)) ||
+            (caps.takeover_imperium && owner_is_imperium)
+    }
+
+    /// How strongly this player defends a world in their tableau: its
+    /// defense, plus their military if positive, plus `TakeoverDefense`.
+    pub fn takeover_defense(&self, card: &cards::Card) -> i32 {
+        let defense = match card.cost {
+            cards::Cost::Military(n) => n,
+            _ => 0,
+        };
+        defense + cmp::max(0, self.military()) + self.get_capabilities().takeover_defense
+    }
+
+    pub fn develop_trade_power(&self) -> i32 {
+        self.get_capabilities().develop_trade_power
+    }
+
+    /// The victory points this player would have if the game ended now.
+    pub fn score(&self) -> i32 {
+        score::tableau_points(&self.tableau, self.military(), self.vp_chips) + self.vp_chips + self.prestige
+    }
+
+    pub fn draw_up_to(&mut self, game: &mut game::Game, up_to: usize) {
         while self.hand.len() < up_to {
-            let c = self.game.borrow_mut().draw();
-            self.hand.push(c);
+            match game.draw() {
+                Some(c) => self.hand.push(c),
+                None => break,
+            }
         }
     }
 
     pub fn print_hand(&self) {
-        if self.hand.len() > 0 {
+        if !self.hand.is_empty() {
             println!("Your hand:");
             for card in self.hand.iter() {
                 println!("    {}", card);
@@ -97,19 +473,92 @@ impl<'a> Player<'a> {
         } else {
             println!("You hand is empty.");
         }
-        println!("");
+        println!();
     }
 
     pub fn print_tableau(&self) {
-        if self.tableau.len() > 0 {
-            println!("Your tableau:");
+        if !self.tableau.is_empty() {
+            println!("Your tableau ({} VP):", self.score());
+            let military = self.military();
             for card in self.tableau.iter() {
-                println!("    {}", card);
+                let flat = score::flat_points(card);
+                let bonus = score::card_points(card, &self.tableau, military, self.vp_chips) - flat;
+                if bonus != 0 {
+                    println!("    {} [{} VP + {} bonus]", card, flat, bonus);
+                } else {
+                    println!("    {} [{} VP]", card, flat);
+                }
             }
         } else {
             println!("You tableau is empty.");
         }
-        println!("");
+        if self.vp_chips > 0 {
+            println!("    VP chips [{} VP]", self.vp_chips);
+        }
+        if self.prestige > 0 {
+            println!("    Prestige [{} VP]", self.prestige);
+        }
+        println!();
+    }
+
+    /// Print what the other players can see: the tableau, VP chips and the
+    /// size of the hand, but not the cards in it.
+    pub fn print_public(&self, name: &str) {
+        println!("{}: {} VP, {} VP chips, {} prestige, {} cards in hand.",
+                 name, self.score(), self.vp_chips, self.prestige, self.hand.len());
+        if !self.tableau.is_empty() {
+            for card in self.tableau.iter() {
+                println!("    {}", card);
+            }
+        } else {
+            println!("    Their tableau is empty.");
+        }
+        println!();
+    }
+
+    /// Describe the powers that affect settling this phase.
+    pub fn print_settle_powers(&self) {
+        let mut caps = self.get_capabilities();
+        caps.settle_military_power = caps.settle_military_power + self.progress.military_bonus;
+
+        println!("You have a military power of {}", caps.settle_military_power.military());
+        println!("You have an effective buying power of {}", caps.settle_trade_power.trade());
+
+        if caps.settle_good_discounts.values().any(|n| { *n > 0 }) ||
+           caps.settle_good_military.values().any(|n| { *n > 0 }) ||
+           caps.settle_attr_military.values().any(|n| { *n > 0 })
+        {
+            println!("Additionally, you have");
+
+            for (option_good, discount) in caps.settle_good_discounts.iter() {
+                if *discount > 0 {
+                    match *option_good {
+                        Some(ref good) => println!("    A {} discount on world that produce {:?}.", discount, good),
+                        None => println!("    A {} discount on worlds which do not product any good.", discount),
+                    }
+                }
+            }
+
+            for (option_good, military) in caps.settle_good_military.iter() {
+                if *military > 0 {
+                    match *option_good {
+                        Some(ref good) => println!("    +{} military on world that produce {:?}.", military, good),
+                        None => println!("    +{} military on worlds which do not product any good.", military),
+                    }
+                }
+            }
+
+            for (attr, military) in caps.settle_attr_military.iter() {
+                if *military > 0 {
+                    println!("    +{} military on world with the {:?} attribute.", military, attr);
+                }
+            }
+        }
+
+        if caps.settle_can_convert_military_to_trade {
+            println!("You may pay for non-Alien military worlds with cards, at a discount of {}.",
+                     caps.settle_conversion_discount);
+        }
     }
 
     fn get_capabilities(&self) -> Capabilities {
@@ -124,6 +573,18 @@ impl<'a> Player<'a> {
                     cards::Power::ExploreKeepBonus(n) => {
                         caps.explore_to_keep += n;
                     },
+                    cards::Power::ExploreSeeBonusIfChosen(n) => {
+                        caps.explore_to_see_if_chosen += n;
+                    },
+                    cards::Power::ExploreKeepBonusIfChosen(n) => {
+                        caps.explore_to_keep_if_chosen += n;
+                    },
+                    cards::Power::ExploreMix => {
+                        caps.explore_mix = true;
+                    },
+                    cards::Power::ExploreDiscardThenSee(n) => {
+                        caps.explore_discard_then_see += n;
+                    },
 
                     cards::Power::DevelopDiscount(n) => {
                         caps.develop_trade_power += n;
@@ -135,19 +596,19 @@ impl<'a> Player<'a> {
 
                     cards::Power::SettleTradeDiscount(n) => {
                         caps.settle_trade_power = caps.settle_trade_power + n;
-                        caps.settle_trade_discount = caps.settle_trade_discount + n;
+                        caps.settle_trade_discount += n;
                     },
                     cards::Power::SettleMilitaryBonus(n) => {
                         caps.settle_military_power = caps.settle_military_power + n;
                     },
                     cards::Power::SettleDiscountIfGood(n, ref good) => {
-                        caps.settle_good_discounts[good.clone()] += n;
+                        *caps.settle_good_discounts.entry(good.clone()).or_insert(0) += n;
                     },
                     cards::Power::SettleMilitaryIfGood(n, ref good) => {
-                        caps.settle_good_military[good.clone()] += n;
+                        *caps.settle_good_military.entry(good.clone()).or_insert(0) += n;
                     },
                     cards::Power::SettleMilitaryIfAttribute(n, ref attr) => {
-                        caps.settle_attr_military[attr.clone()] += n;
+                        *caps.settle_attr_military.entry(attr.clone()).or_insert(0) += n;
                     },
                     cards::Power::SettleMilitaryAsTradeWithDiscount(n) => {
                         caps.settle_can_convert_military_to_trade = true;
@@ -157,13 +618,30 @@ impl<'a> Player<'a> {
                         caps.settle_discard_military.push((card.clone(), n));
                     },
                     cards::Power::SettleDiscardToNegateTradeIfGood(ref good) => {
-                        let g = {
-                            match *good {
-                                Some(ref good) => Some(good.clone()),
-                                None => None,
-                            }
-                        };
-                        caps.settle_discard_to_negate_trade_if_good.push((card.clone(), g));
+                        caps.settle_discard_to_negate_trade_if_good.push((card.clone(), good.clone()));
+                    },
+                    cards::Power::SettleDiscardHandForMilitary(n) => {
+                        caps.settle_hand_discard_military += n;
+                    },
+
+                    cards::Power::TakeoverRebel => {
+                        caps.takeover_rebel = true;
+                    },
+                    cards::Power::TakeoverImperium => {
+                        caps.takeover_imperium = true;
+                    },
+                    cards::Power::TakeoverDefense(n) => {
+                        caps.takeover_defense += n;
+                    },
+
+                    // Prestige is gained once, when the card is placed.
+                    cards::Power::Prestige(_) => {},
+                    cards::Power::SettlePrestigeIfMilitary(n) => {
+                        caps.settle_prestige_if_military += n;
+                    },
+
+                    cards::Power::HandLimitBonus(n) => {
+                        caps.hand_limit += n;
                     },
                 }
             }
@@ -172,206 +650,545 @@ impl<'a> Player<'a> {
         caps
     }
 
-    pub fn act(&mut self, phase: game::Phase) {
-        match phase {
-            game::Phase::Explore => self.explore(),
-            game::Phase::Develop => self.develop(),
-            game::Phase::Settle => self.settle(),
+    /// How the explore phase works for this player. `actions` are the
+    /// explore cards this player chose for the phase. Choosing both gives
+    /// both bonuses.
+    pub fn explore_counts(&self, actions: &[game::Action]) -> ExploreCounts {
+        let caps = self.get_capabilities();
+        let mut see = caps.explore_to_see;
+        let mut keep = caps.explore_to_keep;
+        let mut mix = caps.explore_mix;
+        let mut chosen = false;
+
+        for action in actions.iter() {
+            match *action {
+                game::Action::ExploreSeeFive => {
+                    see += 5;
+                    chosen = true;
+                },
+                game::Action::ExploreMix => {
+                    see += 1;
+                    keep += 1;
+                    mix = true;
+                    chosen = true;
+                },
+                _ => {},
+            }
+        }
+        // Powers for choosing Explore apply once, however many explore cards
+        // were chosen.
+        if chosen {
+            see += caps.explore_to_see_if_chosen;
+            keep += caps.explore_to_keep_if_chosen;
+        }
+
+        let discards = self.progress.explore_discards;
+        ExploreCounts {
+            see: cmp::max(0, see) as usize + discards,
+            keep: cmp::max(0, keep) as usize,
+            mix,
+            discard_then_see: (cmp::max(0, caps.explore_discard_then_see) as usize).saturating_sub(discards),
         }
     }
 
-    fn explore(&mut self) {
-        let mut explore_cards: Vec<cards::Card> = vec![];
-        let mut game = self.game.borrow_mut();
-        let caps = self.get_capabilities();
+    /// Whether `keep`, as indices into the explored cards, is a set of cards
+    /// that could be kept.
+    pub fn is_valid_explore_keep(&self, keep: &[usize]) -> bool {
+        distinct_indices(self.progress.explored.len(), keep) && keep.len() == self.progress.explore_keep
+    }
 
-        for _ in 0..(caps.explore_to_see) {
-            explore_cards.push(game.draw());
-        }
+    /// Whether `discard`, as indices into the hand, is a set of `count` cards
+    /// that could be discarded. Used when mixing explored cards and for the
+    /// hand limit.
+    pub fn is_valid_discard(&self, count: usize, discard: &[usize]) -> bool {
+        distinct_indices(self.hand.len(), discard) && discard.len() == count
+    }
 
-        println!("Choose cards to keep.");
-        let keep_cards = { utils::select_many(&explore_cards, caps.explore_to_keep as usize) };
-        for card in keep_cards {
-            self.hand.push(card);
+    /// How many cards this player must discard at the end of the round.
+    pub fn hand_limit_excess(&self) -> usize {
+        let limit = cmp::max(0, self.get_capabilities().hand_limit) as usize;
+        self.hand.len().saturating_sub(limit)
+    }
+
+    /// Every development in hand that can be afforded. Bonus cards from
+    /// `DevelopDraw` powers should already be in hand. Choosing the Develop
+    /// action takes one off the cost.
+    pub fn develop_options(&self, action: Option<&game::Action>) -> Vec<DevelopOption> {
+        let mut caps = self.get_capabilities();
+        if action == Some(&game::Action::Develop) {
+            caps.develop_trade_discount += 1;
         }
-        explore_cards.retain(|c| { !self.hand.contains(c) });
-        for card in explore_cards {
-            game.discard(card);
+        let mut options = vec![];
+
+        for (index, card) in self.hand.iter().enumerate() {
+            if card.card_type != cards::CardType::Development {
+                continue;
+            }
+            if let Some(price) = caps.develop_price(card) {
+                options.push(DevelopOption { index, card: card.clone(), price });
+            }
         }
+
+        options
     }
 
-    fn develop(&mut self) {
+    /// Every card in the tableau that can be discarded for military, then
+    /// every card in hand while `SettleDiscardHandForMilitary` powers allow.
+    pub fn military_discard_options(&self) -> Vec<MilitaryDiscard> {
         let caps = self.get_capabilities();
+        let mut options: Vec<MilitaryDiscard> = caps.settle_discard_military.into_iter()
+            .map(|(card, military)| { MilitaryDiscard { card, military, from_hand: false } })
+            .collect();
 
-        if caps.develop_draw_before > 0 {
-            println!("Drawing {} cards.", caps.develop_draw_before);
-            let mut game_ref = self.game.borrow_mut();
-            for _ in 0..(caps.develop_draw_before) {
-                let card = game_ref.draw();
-                println!("    {}", card);
-                self.hand.push(card);
+        let hand_discards = cmp::max(0, caps.settle_hand_discard_military) as usize;
+        if self.progress.hand_military_discards < hand_discards {
+            for card in self.hand.iter() {
+                options.push(MilitaryDiscard { card: card.clone(), military: 1, from_hand: true });
             }
         }
 
-        println!("You have an effective trade power of {}", caps.develop_trade_power);
-        println!("What would you like to develop?");
-        println!("");
+        options
+    }
 
-        let choice: Option<cards::Card>;
-        loop {
-            let development_choices: Vec<&cards::Card> =
-                self.hand.as_slice().iter()
-                    .filter(|c| { c.card_type == cards::CardType::Development })
-                    .collect();
+    /// Every world in hand that can be settled, once for each way of paying
+    /// for it, including military gained earlier in the phase.
+    pub fn settle_options(&self) -> Vec<SettleOption> {
+        let mut caps = self.get_capabilities();
+        caps.settle_military_power = caps.settle_military_power + self.progress.military_bonus;
+        let mut options = vec![];
 
-            match utils::select_optional(&development_choices) {
-                None => {
-                    choice = None;
-                    break;
-                },
-                Some(card_ref) => {
-                    if card_ref.cost > caps.develop_trade_power {
-                        println!("You can't afford that card.");
-                    } else {
-                        choice = Some((*card_ref).clone());
-                        break;
-                    }
-                },
+        for (index, card) in self.hand.iter().enumerate() {
+            if card.card_type != cards::CardType::World {
+                continue;
             }
-        };
+            for method in caps.settle_methods(card) {
+                options.push(SettleOption { index, card: card.clone(), method });
+            }
+        }
 
-        match choice {
-            None => {},
-            Some(card) => {
-                self.tableau.push(card.clone());
-                self.hand.retain(|c| { *c != card });
-                self.pay_trade_cost((card.cost - caps.settle_trade_discount).trade());
-            },
+        options
+    }
+
+    /// Whether `payment`, as indices into the hand, could pay `price`. The
+    /// card at `placed` is the one being paid for, so it can't be used.
+    pub fn is_valid_payment(&self, placed: usize, price: i32, payment: &[usize]) -> bool {
+        distinct_indices(self.hand.len(), payment)
+            && payment.len() == cmp::max(0, price) as usize
+            && !payment.contains(&placed)
+    }
+
+    /// Discard a card from hand before exploring, to see one more card.
+    pub(crate) fn explore_discard(&mut self, game: &mut game::Game, index: usize) -> error::Result<()> {
+        if self.progress.has_explored {
+            return Err(RftgError::IllegalMove("Cards have already been explored.".to_string()));
         }
+        if self.explore_counts(&[]).discard_then_see == 0 {
+            return Err(RftgError::IllegalMove("You can't discard any more cards to explore.".to_string()));
+        }
+
+        for card in take_indices(&mut self.hand, &[index])? {
+            self.discard_face_down(game, card);
+        }
+        self.progress.explore_discards += 1;
+        Ok(())
     }
 
-    fn settle(&mut self) {
+    /// Draw the cards to explore. When mixing, they go straight into the hand.
+    pub(crate) fn explore(&mut self, game: &mut game::Game, actions: &[game::Action]) -> error::Result<()> {
+        if self.progress.has_explored {
+            return Err(RftgError::IllegalMove("Cards have already been explored.".to_string()));
+        }
+
+        let counts = self.explore_counts(actions);
+        let mut explored = vec![];
+        for _ in 0..counts.see {
+            match game.draw() {
+                Some(card) => explored.push(card),
+                None => break,
+            }
+        }
+
+        self.progress.has_explored = true;
+        self.progress.explore_keep = cmp::min(counts.keep, explored.len());
+        self.progress.mixing = counts.mix;
+        if counts.mix {
+            self.hand.extend(explored.iter().cloned());
+        }
+        self.progress.explored = explored;
+        Ok(())
+    }
+
+    /// Keep some explored cards, as indices into the explored cards, and
+    /// discard the rest.
+    pub(crate) fn explore_keep(&mut self, game: &mut game::Game, keep: &[usize]) -> error::Result<()> {
+        if !self.progress.has_explored {
+            return Err(RftgError::IllegalMove("You haven't explored yet.".to_string()));
+        }
+        if self.progress.mixing {
+            return Err(RftgError::IllegalMove("Explored cards are mixed into your hand.".to_string()));
+        }
+        if keep.len() != self.progress.explore_keep {
+            return Err(RftgError::IllegalMove(format!("You must keep {} cards.", self.progress.explore_keep)));
+        }
+
+        let mut explored = self.progress.explored.clone();
+        let kept = take_indices(&mut explored, keep)?;
+        self.hand.extend(kept);
+        for card in explored {
+            self.discard_face_down(game, card);
+        }
+        self.finish_explore();
+        Ok(())
+    }
+
+    /// Finish a mixed explore by discarding cards from the combined hand.
+    pub(crate) fn explore_mix_discard(&mut self, game: &mut game::Game, discard: &[usize]) -> error::Result<()> {
+        if !self.progress.mixing {
+            return Err(RftgError::IllegalMove("You are not mixing explored cards.".to_string()));
+        }
+        if discard.len() != self.mix_discards() {
+            return Err(RftgError::IllegalMove(format!("You must discard {} cards.", self.mix_discards())));
+        }
+
+        for card in take_indices(&mut self.hand, discard)? {
+            self.discard_face_down(game, card);
+        }
+        self.finish_explore();
+        Ok(())
+    }
+
+    fn finish_explore(&mut self) {
+        self.progress.explore_discards = 0;
+        self.progress.has_explored = false;
+        self.progress.explored = vec![];
+        self.progress.explore_keep = 0;
+        self.progress.mixing = false;
+    }
+
+    /// Draw the cards granted by `DevelopDraw` powers, before a development
+    /// is chosen.
+    pub(crate) fn develop_draw(&mut self, game: &mut game::Game) -> error::Result<()> {
+        if self.progress.develop_drawn {
+            return Err(RftgError::IllegalMove("You have already drawn for this phase.".to_string()));
+        }
         let caps = self.get_capabilities();
 
-        println!("You have a military power of {}", caps.settle_military_power.military());
-        println!("You have an effective buying power of {}", caps.settle_trade_power.trade());
+        for _ in 0..(caps.develop_draw_before) {
+            match game.draw() {
+                Some(card) => self.hand.push(card),
+                None => break,
+            }
+        }
 
-        if caps.settle_good_discounts.values().any(|n| { *n > 0 }) ||
-           caps.settle_good_military.values().any(|n| { *n > 0 }) ||
-           caps.settle_attr_military.values().any(|n| { *n > 0 })
-        {
-            println!("Additionally, you have");
+        self.progress.develop_drawn = true;
+        Ok(())
+    }
 
-            for (option_good, discount) in caps.settle_good_discounts.iter() {
-                if *discount > 0 {
-                    match *option_good {
-                        Some(ref good) => println!("    A {} discount on world that produce {:?}.", discount, good),
-                        None => println!("    A {} discount on worlds which do not product any good.", discount),
-                    }
+    /// Place a development and pay for it, or pass.
+    pub(crate) fn develop(&mut self, game: &mut game::Game, placement: Option<&Placement>,
+                          action: Option<&game::Action>) -> error::Result<()> {
+        if let Some(placement) = placement {
+            let price = match self.develop_options(action).into_iter().find(|o| { o.index == placement.index }) {
+                Some(option) => option.price,
+                None => return Err(RftgError::IllegalMove("You can't develop that card.".to_string())),
+            };
+            self.place(game, placement, price)?;
+        }
+
+        self.progress.develop_drawn = false;
+        Ok(())
+    }
+
+    /// Discard a card from the tableau or hand for extra military this phase.
+    pub(crate) fn military_discard(&mut self, game: &mut game::Game, discard: &MilitaryDiscard) -> error::Result<()> {
+        if !self.military_discard_options().contains(discard) {
+            return Err(RftgError::IllegalMove(format!("{} can't be discarded for military.", discard.card.name)));
+        }
+
+        if discard.from_hand {
+            let index = match self.hand.iter().position(|c| { *c == discard.card }) {
+                Some(index) => index,
+                None => return Err(RftgError::InvalidData(format!("{} is not in the hand.", discard.card.name))),
+            };
+            game.discard_face_up(self.hand.remove(index));
+            self.progress.hand_military_discards += 1;
+        } else {
+            self.discard_from_tableau(game, &discard.card)?;
+        }
+        self.progress.military_bonus += discard.military;
+        Ok(())
+    }
+
+    /// Settle a world using the given method and pay for it, or pass. Having
+    /// chosen the Settle action, the player draws a card after settling.
+    pub(crate) fn settle(&mut self, game: &mut game::Game, choice: Option<(&Placement, &SettleMethod)>,
+                         action: Option<&game::Action>) -> error::Result<()> {
+        let result = match choice {
+            None => Ok(()),
+            Some((placement, method)) => {
+                let legal = self.settle_options().into_iter()
+                    .any(|o| { o.index == placement.index && o.method == *method });
+                if !legal {
+                    return Err(RftgError::IllegalMove("You can't settle that world that way.".to_string()));
                 }
-            }
 
-            for (option_good, military) in caps.settle_good_military.iter() {
-                if *military > 0 {
-                    match *option_good {
-                        Some(ref good) => println!("    +{} military on world that produce {:?}.", military, good),
-                        None => println!("    +{} military on worlds which do not product any good.", military),
+                let prestige = self.get_capabilities().settle_prestige_if_military;
+                self.place(game, placement, method.price())?;
+                if *method == SettleMethod::Military {
+                    self.prestige += prestige;
+                }
+                if action == Some(&game::Action::Settle) {
+                    if let Some(card) = game.draw() {
+                        self.hand.push(card);
                     }
                 }
-            }
-
-            for (attr, military) in caps.settle_attr_military.iter() {
-                if *military > 0 {
-                    println!("    +{} military on world with the {:?} attribute.", military, attr);
+                match *method {
+                    SettleMethod::DiscardForFree(ref discard) => self.discard_from_tableau(game, discard),
+                    _ => Ok(()),
                 }
+            },
+        };
+
+        self.finish_settle();
+        result
+    }
+
+    /// Forget the military gained this phase, once the player has settled
+    /// or attempted a takeover.
+    pub(crate) fn finish_settle(&mut self) {
+        self.progress.military_bonus = 0;
+        self.progress.hand_military_discards = 0;
+    }
+
+    /// Use the Search action: flip cards until one matches `category`.
+    pub(crate) fn search(&mut self, game: &mut game::Game, category: &cards::SearchCategory) -> error::Result<()> {
+        if self.has_searched {
+            return Err(RftgError::IllegalMove("You have already searched this game.".to_string()));
+        }
+
+        self.has_searched = true;
+        self.progress.search_found = game.flip_until(|card| { category.matches(card) });
+        self.progress.search_category = Some(category.clone());
+        Ok(())
+    }
+
+    /// Keep the card found by searching, or discard it and take the next
+    /// card that matches instead.
+    pub(crate) fn search_keep(&mut self, game: &mut game::Game, keep: bool) -> error::Result<()> {
+        let (found, category) = match (self.progress.search_found.take(), self.progress.search_category.take()) {
+            (Some(found), Some(category)) => (found, category),
+            _ => return Err(RftgError::IllegalMove("You haven't found a card by searching.".to_string())),
+        };
+
+        if keep {
+            self.hand.push(found);
+        } else {
+            game.discard_face_up(found);
+            if let Some(next) = game.flip_until(|card| { category.matches(card) }) {
+                self.hand.push(next);
             }
         }
+        Ok(())
+    }
+
+    /// Take VP chips, such as for being the prestige leader.
+    pub(crate) fn gain_vp_chips(&mut self, n: i32) {
+        self.vp_chips += n;
+    }
 
+    /// Remove a world from the tableau after it is taken over.
+    pub(crate) fn lose_world(&mut self, card: &cards::Card) -> error::Result<cards::Card> {
+        match self.tableau.iter().position(|c| { c == card }) {
+            Some(index) => Ok(self.tableau.remove(index)),
+            None => Err(RftgError::InvalidData(format!("{} is not in the tableau.", card.name))),
+        }
+    }
 
-        let choice: Option<cards::Card>;
-        loop {
-            let settle_choices: Vec<&cards::Card> =
-                self.hand.as_slice().iter()
-                    .filter(|c| { c.card_type == cards::CardType::World })
-                    .collect();
-            match utils::select_optional(&settle_choices) {
-                None => {
-                    choice = None;
-                    break;
-                },
-                Some(card_ref) => {
-                    let mut real_cost = card_ref.cost.clone();
-
-                    match (card_ref.cost.clone(), card_ref.produces.clone()) {
-                        (cards::Cost::Military(_), Some((_, good))) => {
-                            real_cost = real_cost - caps.settle_good_military[Some(good)];
-                        },
-                        (cards::Cost::Military(_), None) => {
-                            let none: Option<cards::Good> = None;
-                            real_cost = real_cost - caps.settle_good_military[none];
-                        },
-                        (cards::Cost::Trade(_), Some((_, good))) => {
-                            real_cost = real_cost - caps.settle_good_discounts[Some(good)];
-                        },
-                        (cards::Cost::Trade(_), None) => {
-                            let none: Option<cards::Good> = None;
-                            real_cost = real_cost - caps.settle_good_discounts[none];
-                        },
-                        (cards::Cost::Free, _) => {},
-                    }
+    pub(crate) fn gain_world(&mut self, card: cards::Card) {
+        self.tableau.push(card);
+    }
 
-                    match card_ref.cost {
-                        cards::Cost::Military(_) => {
-                            for ref attr in card_ref.attributes.iter() {
-                                real_cost = real_cost - caps.settle_attr_military[**attr];
-                            }
-                        },
-                        _ => {},
-                    }
+    /// Move a card from hand to the tableau, discarding the payment.
+    fn place(&mut self, game: &mut game::Game, placement: &Placement, price: i32) -> error::Result<()> {
+        if placement.payment.len() != cmp::max(0, price) as usize {
+            return Err(RftgError::IllegalMove(format!("You must pay with {} cards.", price)));
+        }
+        if placement.payment.contains(&placement.index) {
+            return Err(RftgError::IllegalMove("A card can't pay for itself.".to_string()));
+        }
 
-                    println!("Card's real cost: {:?}", real_cost);
-                    println!("real_cost > trade: {}", real_cost > caps.settle_trade_power);
-                    println!("real_cost > military: {}", real_cost > caps.settle_military_power);
+        let mut indices = placement.payment.clone();
+        indices.push(placement.index);
+        let taken = take_indices(&mut self.hand, &indices)?;
 
-                    if real_cost > caps.settle_trade_power || real_cost > caps.settle_military_power {
-                        println!("You can't afford that card.");
-                    } else {
-                        choice = Some((*card_ref).clone());
-                        break;
+        for (index, card) in indices.into_iter().zip(taken) {
+            if index == placement.index {
+                for power in card.powers.iter() {
+                    if let cards::Power::Prestige(n) = *power {
+                        self.prestige += n;
                     }
                 }
+                self.tableau.push(card);
+            } else {
+                self.discard_face_down(game, card);
             }
-        };
+        }
+        Ok(())
+    }
 
-        match choice {
-            None => {},
-            Some(card) => {
-                self.tableau.push(card.clone());
-                self.hand.retain(|c| { *c != card });
+    fn discard_face_down(&mut self, game: &mut game::Game, card: cards::Card) {
+        self.face_down_discards.push((game.reshuffles(), card.clone()));
+        game.discard(card);
+    }
 
-                match card.cost {
-                    cards::Cost::Trade(c) => self.pay_trade_cost(c),
-                    cards::Cost::Military(_) => println!("Your military conquers the world."),
-                    cards::Cost::Free => println!("You settle the world for free."),
-                }
+    fn discard_from_tableau(&mut self, game: &mut game::Game, card: &cards::Card) -> error::Result<()> {
+        match self.tableau.iter().position(|c| { c == card }) {
+            Some(index) => {
+                let discarded = self.tableau.remove(index);
+                game.discard_face_up(discarded);
+                Ok(())
             },
+            None => Err(RftgError::InvalidData(format!("{} is not in the tableau.", card.name))),
         }
     }
 
-    fn pay_trade_cost(&mut self, price_to_pay: i32) {
-        if price_to_pay > 0 {
-            println!("Choose cards to use as payment.");
-            let payment_cards = { utils::select_many(&self.hand, price_to_pay as usize) };
+    /// Discard cards, as indices into the hand, to get within the hand limit.
+    pub(crate) fn hand_limit_discard(&mut self, game: &mut game::Game, discard: &[usize]) -> error::Result<()> {
+        if discard.len() != self.hand_limit_excess() {
+            return Err(RftgError::IllegalMove(format!("You must discard {} cards.", self.hand_limit_excess())));
+        }
 
-            let size_before = self.hand.len();
-            self.hand.retain(|c| { !payment_cards.contains(&c) });
-            assert!(size_before - (price_to_pay as usize) == self.hand.len());
+        for card in take_indices(&mut self.hand, discard)? {
+            self.discard_face_down(game, card);
+        }
+        Ok(())
+    }
+}
 
-            for payment in payment_cards {
-                self.game.borrow_mut().discard(payment.clone());
-            }
-        } else {
-            println!("Your cost is 0.");
+/// Whether every index is below `len` and none is repeated.
+fn distinct_indices(len: usize, indices: &[usize]) -> bool {
+    indices.iter().enumerate().all(|(i, &index)| { index < len && !indices[..i].contains(&index) })
+}
+
+/// Remove the cards at `indices` from `cards`, returning them in the same
+/// order as `indices`.
+fn take_indices(cards: &mut Vec<cards::Card>, indices: &[usize]) -> error::Result<Vec<cards::Card>> {
+    for (i, &index) in indices.iter().enumerate() {
+        if index >= cards.len() {
+            return Err(RftgError::IllegalMove(format!("There is no card {}.", index + 1)));
         }
+        if indices[..i].contains(&index) {
+            return Err(RftgError::IllegalMove(format!("Card {} was chosen twice.", index + 1)));
+        }
+    }
+
+    let taken: Vec<cards::Card> = indices.iter().map(|&index| { cards[index].clone() }).collect();
+    let mut sorted = indices.to_vec();
+    sorted.sort();
+    for index in sorted.into_iter().rev() {
+        cards.remove(index);
+    }
+    Ok(taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{self, test_cards};
+    use crate::game;
+    use super::Player;
+
+    fn with_power(name: &str, power: cards::Power) -> cards::Card {
+        cards::Card { powers: vec![power], ..test_cards::development(name, 1) }
+    }
+
+    #[test]
+    fn develop_without_discounts() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(3, 1);
+
+        let caps = player.get_capabilities();
+        assert_eq!(caps.develop_price(&test_cards::development("Cheap", 2)), Some(2));
+        assert_eq!(caps.develop_price(&test_cards::development("Expensive", 3)), None);
+    }
+
+    #[test]
+    fn develop_discount_power() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(3, 1);
+        player.tableau.push(with_power("Discounter", cards::Power::DevelopDiscount(1)));
+
+        let caps = player.get_capabilities();
+        assert_eq!(caps.develop_price(&test_cards::development("Expensive", 3)), Some(2));
+    }
+
+    #[test]
+    fn develop_discounts_stack() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(2, 1);
+        player.tableau.push(with_power("Discounter A", cards::Power::DevelopDiscount(1)));
+        player.tableau.push(with_power("Discounter B", cards::Power::DevelopDiscount(2)));
+
+        let caps = player.get_capabilities();
+        assert_eq!(caps.develop_price(&test_cards::development("Expensive", 4)), Some(1));
+    }
+
+    #[test]
+    fn develop_discount_never_goes_below_zero() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(1, 1);
+        player.tableau.push(with_power("Discounter", cards::Power::DevelopDiscount(3)));
+
+        let caps = player.get_capabilities();
+        assert_eq!(caps.develop_price(&test_cards::development("Cheap", 1)), Some(0));
+    }
+
+    #[test]
+    fn settle_discount_does_not_apply_to_develop() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(3, 1);
+        player.tableau.push(with_power("Settler", cards::Power::SettleTradeDiscount(2)));
+
+        let caps = player.get_capabilities();
+        assert_eq!(caps.develop_price(&test_cards::development("Expensive", 3)), None);
+    }
+
+    #[test]
+    fn develop_draw_happens_before_paying() {
+        let mut game = game::Game::from_deck(test_cards::developments(5, 1), 1);
+        let mut player = Player::new();
+        player.hand = test_cards::developments(2, 1);
+        player.tableau.push(with_power("Drawer", cards::Power::DevelopDraw(1)));
+
+        let expensive = test_cards::development("Expensive", 2);
+        assert_eq!(player.get_capabilities().develop_price(&expensive), None);
+
+        player.develop_draw(&mut game).unwrap();
+        assert_eq!(player.hand.len(), 3);
+        assert_eq!(player.get_capabilities().develop_price(&expensive), Some(2));
+    }
+
+    #[test]
+    fn valid_payments() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(4, 1);
+
+        assert!(player.is_valid_payment(0, 2, &[1, 3]));
+        assert!(player.is_valid_payment(0, 0, &[]));
+        assert!(player.is_valid_payment(0, -1, &[]));
+        assert!(!player.is_valid_payment(0, 2, &[1]));
+        assert!(!player.is_valid_payment(0, 2, &[0, 1]));
+        assert!(!player.is_valid_payment(0, 2, &[2, 2]));
+        assert!(!player.is_valid_payment(0, 2, &[1, 4]));
+    }
+
+    #[test]
+    fn valid_discards() {
+        let mut player = Player::new();
+        player.hand = test_cards::developments(3, 1);
+
+        assert!(player.is_valid_discard(2, &[2, 0]));
+        assert!(!player.is_valid_discard(2, &[0]));
+        assert!(!player.is_valid_discard(2, &[1, 1]));
+        assert!(!player.is_valid_discard(1, &[3]));
     }
 }
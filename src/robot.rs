@@ -0,0 +1,143 @@
+use std::fmt;
+use rand::{thread_rng, Rng};
+
+use crate::cards;
+use crate::game;
+use crate::score;
+
+/// The robot opponent for the solo game, a simplified version of the one
+/// from Gathering Storm.
+///
+/// Each round the robot rolls a die for its action. It takes part in every
+/// phase, by its own rules rather than a hand of cards:
+///
+/// * Explore: it gains 1 credit, or 3 if it chose Explore.
+/// * Develop: it flips cards until a development, and places it if it has
+///   enough credits, one fewer if it chose Develop. Otherwise the card is
+///   discarded and it gains a credit.
+/// * Settle: it flips cards until a world. Non-military worlds are paid for
+///   with credits like developments. Military worlds are conquered if its
+///   military, plus one if it chose Settle, is at least the defense.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Robot {
+    tableau: Vec<cards::Card>,
+    credits: i32,
+}
+
+/// What the robot did in a phase.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RobotTurn {
+    Credits(i32),
+    Placed(cards::Card),
+    Discarded(cards::Card),
+    NothingFound,
+}
+
+impl fmt::Display for RobotTurn {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            RobotTurn::Credits(n) => write!(fmt, "The robot gains {} credits.", n),
+            RobotTurn::Placed(ref card) => write!(fmt, "The robot places {}.", card),
+            RobotTurn::Discarded(ref card) => write!(fmt, "The robot can't afford {} and gains a credit.", card.name),
+            RobotTurn::NothingFound => write!(fmt, "The robot finds nothing to place."),
+        }
+    }
+}
+
+impl Robot {
+    pub fn new() -> Robot {
+        Default::default()
+    }
+
+    pub fn tableau(&self) -> &[cards::Card] {
+        &self.tableau
+    }
+
+    pub fn credits(&self) -> i32 {
+        self.credits
+    }
+
+    /// Military from the `SettleMilitaryBonus` powers in the robot's tableau.
+    pub fn military(&self) -> i32 {
+        self.tableau.iter()
+            .flat_map(|card| { card.powers.iter() })
+            .map(|power| { match *power { cards::Power::SettleMilitaryBonus(n) => n, _ => 0 } })
+            .sum()
+    }
+
+    pub fn score(&self) -> i32 {
+        score::tableau_points(&self.tableau, self.military(), 0)
+    }
+
+    /// Roll the robot's action die: 1 or 2 explores, 3 or 4 develops, and 5
+    /// or 6 settles.
+    pub fn roll_action() -> game::Action {
+        match thread_rng().gen_range(1..=6) {
+            1 | 2 => game::Action::ExploreSeeFive,
+            3 | 4 => game::Action::Develop,
+            _ => game::Action::Settle,
+        }
+    }
+
+    /// Play a phase by the robot rules. `chose` is whether the robot chose
+    /// the action for this phase.
+    pub(crate) fn play_phase(&mut self, game: &mut game::Game, phase: &game::Phase, chose: bool) -> RobotTurn {
+        let bonus = if chose { 1 } else { 0 };
+
+        match *phase {
+            game::Phase::Explore => {
+                let credits = 1 + 2 * bonus;
+                self.credits += credits;
+                RobotTurn::Credits(credits)
+            },
+            game::Phase::Develop => {
+                let card = game.flip_until(|card| { card.card_type == cards::CardType::Development });
+                self.place(game, card, |robot, card| {
+                    match card.cost {
+                        cards::Cost::Trade(cost) => robot.pay(cost - bonus),
+                        _ => robot.pay(0),
+                    }
+                })
+            },
+            game::Phase::Settle => {
+                let card = game.flip_until(|card| { card.card_type == cards::CardType::World });
+                self.place(game, card, |robot, card| {
+                    match card.cost {
+                        cards::Cost::Military(defense) => robot.military() + bonus >= defense,
+                        cards::Cost::Trade(cost) => robot.pay(cost),
+                        cards::Cost::Free => true,
+                    }
+                })
+            },
+        }
+    }
+
+    /// Spend credits, if there are enough.
+    fn pay(&mut self, cost: i32) -> bool {
+        let cost = cost.max(0);
+        if self.credits >= cost {
+            self.credits -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn place<F>(&mut self, game: &mut game::Game, card: Option<cards::Card>, afford: F) -> RobotTurn
+        where F: Fn(&mut Robot, &cards::Card) -> bool,
+    {
+        match card {
+            None => RobotTurn::NothingFound,
+            Some(card) => {
+                if afford(self, &card) {
+                    self.tableau.push(card.clone());
+                    RobotTurn::Placed(card)
+                } else {
+                    self.credits += 1;
+                    game.discard_face_up(card.clone());
+                    RobotTurn::Discarded(card)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::cards;
+
+/// Victory points from every card in a tableau, not counting the chips
+/// themselves.
+pub fn tableau_points(tableau: &[cards::Card], military: i32, vp_chips: i32) -> i32 {
+    tableau.iter()
+        .map(|card| { card_points(card, tableau, military, vp_chips) })
+        .sum()
+}
+
+/// The victory points a single card is worth in the given tableau.
+pub fn card_points(card: &cards::Card, tableau: &[cards::Card], military: i32, vp_chips: i32) -> i32 {
+    card.victory_points.iter()
+        .map(|points| { points_value(points, tableau, military, vp_chips) })
+        .sum()
+}
+
+/// The part of a card's points that is printed on it as a flat value, as
+/// opposed to bonuses that depend on the rest of the tableau.
+pub fn flat_points(card: &cards::Card) -> i32 {
+    card.victory_points.iter()
+        .map(|points| { match *points { cards::Points::Simple(n) => n, _ => 0 } })
+        .sum()
+}
+
+fn points_value(points: &cards::Points, tableau: &[cards::Card], military: i32, vp_chips: i32) -> i32 {
+    match *points {
+        cards::Points::Simple(n) => n,
+        cards::Points::TableauConditions(n, ref conditions) => {
+            let count = tableau.iter()
+                .filter(|card| { conditions.iter().all(|cond| { matches(cond, card) }) })
+                .count();
+            n * count as i32
+        },
+        cards::Points::Military => military,
+        cards::Points::PerVPChip(n) => if n > 0 { vp_chips / n } else { 0 },
+    }
+}
+
+/// Whether a tableau card satisfies a scoring condition.
+pub fn matches(condition: &cards::Condition, card: &cards::Card) -> bool {
+    match *condition {
+        cards::Condition::CardType(ref card_type) => card.card_type == *card_type,
+        cards::Condition::PowerType(ref power_type) =>
+            card.powers.iter().any(|p| { p.power_type().as_ref() == Some(power_type) }),
+        cards::Condition::Named(ref name) => card.name == *name,
+        cards::Condition::Attribute(ref attr) => card.attributes.contains(attr),
+        cards::Condition::MinCost(ref cost) => cost_value(&card.cost) >= cost_value(cost),
+        cards::Condition::MilitaryWorld =>
+            card.card_type == cards::CardType::World && matches!(card.cost, cards::Cost::Military(_)),
+        cards::Condition::Produces(ref production, ref good) =>
+            card.produces == Some((production.clone(), good.clone())),
+        cards::Condition::Good(ref good) => match card.produces {
+            Some((_, ref produced)) => produced == good,
+            None => false,
+        },
+        cards::Condition::Not(ref inner) => !matches(inner, card),
+    }
+}
+
+/// The number on a card's cost, whether it is paid in trade or military.
+pub fn cost_value(cost: &cards::Cost) -> i32 {
+    match *cost {
+        cards::Cost::Trade(n) => n,
+        cards::Cost::Military(n) => n,
+        cards::Cost::Free => 0,
+    }
+}
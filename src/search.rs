@@ -0,0 +1,229 @@
+use std::ops;
+
+use crate::cards;
+use crate::error::{self, RftgError};
+use crate::score;
+use crate::utils;
+
+/// A filter over the card catalogue. Every criterion that is set has to
+/// match.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    name: Option<String>,
+    card_type: Option<cards::CardType>,
+    good: Option<cards::Good>,
+    attribute: Option<cards::Attribute>,
+    expansion: Option<cards::Expansion>,
+    cost: Option<ops::RangeInclusive<i32>>,
+    power: Option<String>,
+}
+
+/// One distinct card from the catalogue, and how many copies are in the
+/// deck.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub card: cards::Card,
+    pub count: usize,
+}
+
+impl Query {
+    pub fn new() -> Query {
+        Default::default()
+    }
+
+    /// Match cards whose name contains this, ignoring case.
+    pub fn name(mut self, name: &str) -> Query {
+        self.name = Some(name.to_lowercase());
+        self
+    }
+
+    pub fn card_type(mut self, card_type: cards::CardType) -> Query {
+        self.card_type = Some(card_type);
+        self
+    }
+
+    /// Match worlds that produce or windfall this good.
+    pub fn good(mut self, good: cards::Good) -> Query {
+        self.good = Some(good);
+        self
+    }
+
+    pub fn attribute(mut self, attribute: cards::Attribute) -> Query {
+        self.attribute = Some(attribute);
+        self
+    }
+
+    pub fn expansion(mut self, expansion: cards::Expansion) -> Query {
+        self.expansion = Some(expansion);
+        self
+    }
+
+    /// Match cards whose trade or military cost is in this range.
+    pub fn cost(mut self, cost: ops::RangeInclusive<i32>) -> Query {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Match cards with a power of this kind. The kind is either a phase,
+    /// like "settle", or part of a power's name, like "discount".
+    pub fn power(mut self, kind: &str) -> Query {
+        self.power = Some(kind.to_lowercase());
+        self
+    }
+
+    /// Build a query from command line style arguments: `--type`, `--good`,
+    /// `--attribute`, `--expansion`, `--cost MIN-MAX` and `--power` take a
+    /// value, and anything else is part of the name.
+    pub fn parse(args: &[String]) -> error::Result<Query> {
+        let mut query = Query::new();
+        let mut name: Vec<&str> = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || -> error::Result<&String> {
+                args.next().ok_or_else(|| { RftgError::InvalidData(format!("{} needs a value.", arg)) })
+            };
+
+            query = match arg.as_str() {
+                "--type" => query.card_type(parse_filter(value()?)?),
+                "--good" => query.good(parse_filter(value()?)?),
+                "--attribute" => query.attribute(parse_filter(value()?)?),
+                "--expansion" => query.expansion(parse_filter(value()?)?),
+                "--cost" => query.cost(parse_cost(value()?)?),
+                "--power" => query.power(value()?),
+                _ => {
+                    name.push(arg);
+                    query
+                },
+            };
+        }
+
+        if !name.is_empty() {
+            query = query.name(&name.join(" "));
+        }
+        Ok(query)
+    }
+
+    pub fn matches(&self, card: &cards::Card) -> bool {
+        if let Some(ref name) = self.name {
+            if !card.name.to_lowercase().contains(name.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref card_type) = self.card_type {
+            if card.card_type != *card_type {
+                return false;
+            }
+        }
+        if let Some(ref good) = self.good {
+            if !score::matches(&cards::Condition::Good(good.clone()), card) {
+                return false;
+            }
+        }
+        if let Some(ref attribute) = self.attribute {
+            if !card.attributes.contains(attribute) {
+                return false;
+            }
+        }
+        if let Some(ref expansion) = self.expansion {
+            if card.expansion != *expansion {
+                return false;
+            }
+        }
+        if let Some(ref cost) = self.cost {
+            if !cost.contains(&score::cost_value(&card.cost)) {
+                return false;
+            }
+        }
+        if let Some(ref kind) = self.power {
+            let phase: Option<cards::PowerType> = utils::parse_variant(kind);
+            let has_power = card.powers.iter().any(|power| {
+                match phase {
+                    Some(ref phase) => power.power_type().as_ref() == Some(phase),
+                    None => format!("{:?}", power).to_lowercase().contains(kind.as_str()),
+                }
+            });
+            if !has_power {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Every distinct matching card in the catalogue, in catalogue order.
+    pub fn search(&self, catalogue: &[cards::Card]) -> Vec<Entry> {
+        let mut entries: Vec<Entry> = vec![];
+        for card in catalogue.iter().filter(|card| { self.matches(card) }) {
+            match entries.iter_mut().find(|entry| { entry.card.name == card.name }) {
+                Some(entry) => entry.count += 1,
+                None => entries.push(Entry { card: card.clone(), count: 1 }),
+            }
+        }
+        entries
+    }
+}
+
+impl Entry {
+    /// Print everything about the card: cost, production, points and the
+    /// rules text of each power.
+    pub fn print(&self) {
+        let card = &self.card;
+        match card.expansion {
+            cards::Expansion::Base => println!("{} ({} in the deck)", card.name, self.count),
+            ref expansion => println!("{} ({} in the deck, {:?})", card.name, self.count, expansion),
+        }
+
+        match card.cost {
+            cards::Cost::Military(n) => println!("    {:?}, {} military", card.card_type, n),
+            cards::Cost::Trade(n) => println!("    {:?}, costs {}", card.card_type, n),
+            cards::Cost::Free => println!("    {:?}, free", card.card_type),
+        }
+
+        if let Some((ref production, ref good)) = card.produces {
+            println!("    {:?}: {:?}", production, good);
+        }
+        if !card.attributes.is_empty() {
+            let attributes: Vec<String> = card.attributes.iter().map(|a| { format!("{:?}", a) }).collect();
+            println!("    {}", attributes.join(", "));
+        }
+        for points in card.victory_points.iter() {
+            println!("    {}", describe_points(points));
+        }
+        for power in card.powers.iter() {
+            println!("    {}", power);
+        }
+        println!();
+    }
+}
+
+fn describe_points(points: &cards::Points) -> String {
+    match *points {
+        cards::Points::Simple(n) => format!("{} VP.", n),
+        cards::Points::TableauConditions(n, ref conditions) => {
+            let conditions: Vec<String> = conditions.iter().map(|c| { format!("{:?}", c) }).collect();
+            format!("{} VP for each card in your tableau that matches {}.", n, conditions.join(" and "))
+        },
+        cards::Points::Military => "1 VP for each point of military.".to_string(),
+        cards::Points::PerVPChip(n) => format!("1 VP for every {} VP chips.", n),
+    }
+}
+
+fn parse_filter<T: utils::Variants + std::fmt::Debug>(value: &str) -> error::Result<T> {
+    utils::parse_variant(value).ok_or_else(|| {
+        let names: Vec<String> = T::variants().iter().map(|v| { format!("{:?}", v) }).collect();
+        RftgError::InvalidData(format!("\"{}\" should be one of {}.", value, names.join(", ")))
+    })
+}
+
+/// Parse a cost like "3" or "2-4".
+fn parse_cost(value: &str) -> error::Result<ops::RangeInclusive<i32>> {
+    let invalid = || { RftgError::InvalidData(format!("\"{}\" is not a cost or range of costs.", value)) };
+    let mut bounds = value.splitn(2, '-').map(|n| { n.trim().parse::<i32>().map_err(|_| { invalid() }) });
+
+    let min = bounds.next().ok_or_else(invalid)??;
+    let max = match bounds.next() {
+        Some(max) => max?,
+        None => min,
+    };
+    Ok(min..=max)
+}
@@ -0,0 +1,143 @@
+use crate::error;
+use crate::game::{GameState, Move};
+use crate::goals;
+use crate::robot;
+
+/// A game being played, with the earlier states each player can undo back
+/// to within the current phase.
+///
+/// Every move is kept until something hidden is revealed, such as drawing
+/// cards, since undoing past that would let a player choose again knowing
+/// what comes next. Moves are also forgotten once the phase or round ends,
+/// so goals and prestige that have been handed out stay handed out.
+#[derive(Debug, Clone)]
+pub struct Session {
+    state: GameState,
+    /// The state before each move, and the player who made it.
+    history: Vec<(usize, GameState)>,
+}
+
+impl Session {
+    pub fn new(state: GameState) -> Session {
+        Session { state, history: vec![] }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Make a move, remembering the state before it unless the move can't be
+    /// taken back.
+    pub fn apply(&mut self, mv: Move) -> error::Result<()> {
+        let player = mv.player();
+        let reveals_information = self.state.reveals_information(&mv);
+        let next = self.state.apply(mv)?;
+
+        if reveals_information {
+            self.history.clear();
+        } else {
+            self.history.push((player, self.state.clone()));
+        }
+        self.state = next;
+        Ok(())
+    }
+
+    /// Take back `player`'s last move. Only the latest move can be taken
+    /// back, so this fails once another player has moved since. Returns
+    /// whether there was a move to undo.
+    pub fn undo(&mut self, player: usize) -> bool {
+        match self.history.last() {
+            Some(&(mover, _)) if mover == player => {},
+            _ => return false,
+        }
+        if let Some((_, state)) = self.history.pop() {
+            self.state = state;
+        }
+        true
+    }
+
+    /// Hand out goals once a phase is over, returning the changes in who
+    /// holds which goal.
+    pub fn end_phase(&mut self) -> error::Result<Vec<goals::Claim>> {
+        let (state, claims) = self.state.end_phase()?;
+        self.history.clear();
+        self.state = state;
+        Ok(claims)
+    }
+
+    /// Let the robot, if there is one, play the current phase. The robot
+    /// flips cards, so this can't be undone.
+    pub fn robot_phase(&mut self) -> error::Result<Option<robot::RobotTurn>> {
+        let (state, turn) = self.state.play_robot_phase()?;
+        if turn.is_some() {
+            self.history.clear();
+        }
+        self.state = state;
+        Ok(turn)
+    }
+
+    /// Finish the round, returning the prestige leader if there is one.
+    pub fn end_round(&mut self) -> error::Result<Option<usize>> {
+        let (state, leader) = self.state.end_round()?;
+        self.history.clear();
+        self.state = state;
+        Ok(leader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::test_cards;
+    use crate::game::{Action, GameState, Move, Phase, Step};
+    use crate::player;
+    use super::Session;
+
+    fn developing(players: usize) -> Session {
+        let decks = (0..players).map(|_| { test_cards::developments(10, 2) }).collect();
+        let mut session = Session::new(GameState::drafted(decks));
+        for player in 0..players {
+            session.apply(Move::ChooseActions { player, actions: vec![Action::Develop] }).unwrap();
+        }
+        for player in 0..players {
+            session.apply(Move::DevelopDraw { player }).unwrap();
+        }
+        session
+    }
+
+    #[test]
+    fn undo_a_paid_development_within_the_phase() {
+        let mut session = developing(1);
+        let placement = player::Placement { index: 0, payment: vec![1] };
+        session.apply(Move::Develop { player: 0, placement: Some(placement) }).unwrap();
+
+        assert!(session.undo(0));
+        assert_eq!(session.state().step(), Step::Phase(Phase::Develop));
+        assert!(session.state().player(0).tableau().is_empty());
+        assert_eq!(session.state().player(0).hand().len(), 3);
+        assert!(!session.undo(0));
+    }
+
+    #[test]
+    fn undo_is_refused_after_the_phase_ends() {
+        let mut session = developing(1);
+        let placement = player::Placement { index: 0, payment: vec![1] };
+        session.apply(Move::Develop { player: 0, placement: Some(placement) }).unwrap();
+        session.end_phase().unwrap();
+        assert_eq!(session.state().step(), Step::EndOfRound);
+
+        assert!(!session.undo(0));
+        assert_eq!(session.state().player(0).tableau().len(), 1);
+    }
+
+    #[test]
+    fn only_the_latest_move_can_be_undone() {
+        let mut session = developing(2);
+        session.apply(Move::Develop { player: 0, placement: None }).unwrap();
+        session.apply(Move::Develop { player: 1, placement: None }).unwrap();
+
+        assert!(!session.undo(0));
+        assert!(session.undo(1));
+        assert!(session.undo(0));
+        assert!(!session.state().has_finished_phase(0));
+    }
+}
@@ -1,10 +1,10 @@
-extern crate rand;
-
 use std::{io, str, ops, fmt};
 use std::iter::FromIterator;
 use std::io::Write;
 
-pub trait Variants {
+use crate::error::{Result, RftgError};
+
+pub trait Variants: Sized {
     fn variants() -> Vec<Self>;
 }
 
@@ -36,7 +36,7 @@ impl<T> Contains<T> for ops::RangeFull {
     }
 }
 
-impl<'a, T: Eq> Contains<T> for &'a Vec<T> {
+impl<T: Eq> Contains<T> for &Vec<T> {
     fn contains(&self, needle: &T) -> bool {
         for hay in self.iter() {
             if hay == needle {
@@ -47,13 +47,44 @@ impl<'a, T: Eq> Contains<T> for &'a Vec<T> {
     }
 }
 
-pub fn get_num<T, U>(valid: U) -> T
+/// What was typed at a prompt: an answer, or a request to do something
+/// else first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input<T> {
+    Answer(T),
+    /// The player asked to take back their last decision.
+    Undo,
+    /// The player typed a command, such as `view`, instead of answering.
+    Command(String),
+}
+
+impl<T> Input<T> {
+    /// Change the answer, if there is one.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Input<U> {
+        match self {
+            Input::Answer(answer) => Input::Answer(f(answer)),
+            Input::Undo => Input::Undo,
+            Input::Command(command) => Input::Command(command),
+        }
+    }
+}
+
+pub fn get_num<T, U>(valid: U) -> Result<Input<T>>
     where T: str::FromStr + fmt::Debug + PartialOrd,
           U: Contains<T> + fmt::Debug,
 {
     loop {
         let mut input = String::new();
-        io::stdin().read_line(&mut input).ok().expect("Error reading stdin.");
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Err(RftgError::Io(io::Error::other("End of input.")));
+        }
+
+        if input.trim() == "undo" {
+            return Ok(Input::Undo);
+        }
+        if input.trim().starts_with(|c: char| { c.is_alphabetic() }) {
+            return Ok(Input::Command(input.trim().to_string()));
+        }
 
         let num: T = match input.trim().parse().ok() {
             Some(num) => num,
@@ -68,42 +99,63 @@ pub fn get_num<T, U>(valid: U) -> T
             continue;
         }
 
-        return num
+        return Ok(Input::Answer(num))
     };
 }
 
-pub fn select<'a, T>(source: &'a Vec<T>) -> T
+pub fn select<T>(source: &[T]) -> Result<Input<T>>
     where T: fmt::Display + Clone,
 {
+    Ok(select_index(source)?.map(|index| { source[index].clone() }))
+}
+
+/// Like `select`, but returns the position of the chosen option.
+pub fn select_index<T>(source: &[T]) -> Result<Input<usize>>
+    where T: fmt::Display,
+{
+    if source.is_empty() {
+        return Err(RftgError::InsufficientCards { needed: 1, available: 0 });
+    }
+
     for (i, option) in source.iter().enumerate() {
         println!("    {}) {}", i + 1, option);
     }
-    let choice = get_num(1..(source.len() + 1)) - 1;
-    (&source[choice]).clone()
+    Ok(get_num(1..(source.len() + 1))?.map(|choice: usize| { choice - 1 }))
 }
 
-pub fn select_optional<'a, T>(source: &'a Vec<T>) -> Option<T>
+pub fn select_optional<T>(source: &[T]) -> Result<Input<Option<T>>>
     where T: fmt::Display + Clone,
+{
+    Ok(select_optional_index(source)?.map(|choice| { choice.map(|index| { source[index].clone() }) }))
+}
+
+/// Like `select_optional`, but returns the position of the chosen option.
+pub fn select_optional_index<T>(source: &[T]) -> Result<Input<Option<usize>>>
+    where T: fmt::Display,
 {
     for (i, option) in source.iter().enumerate() {
         println!("    {}) {}", i + 1, option);
     }
     println!("    0) None");
 
-    let choice = get_num(0..(source.len() + 1));
-
-    if choice > 0 {
-        Some((&source[choice - 1]).clone())
-    } else {
-        None
-    }
+    Ok(get_num(0..(source.len() + 1))?.map(|choice: usize| { choice.checked_sub(1) }))
 }
 
-pub fn select_many<'a, T>(source: &'a Vec<T>, count: usize) -> Vec<T>
+pub fn select_many<T>(source: &[T], count: usize) -> Result<Input<Vec<T>>>
     where T: fmt::Display + Clone,
+{
+    Ok(select_many_indices(source, count)?.map(|indices| {
+        indices.into_iter().map(|index| { source[index].clone() }).collect()
+    }))
+}
+
+/// Like `select_many`, but returns the positions of the chosen options, in
+/// ascending order.
+pub fn select_many_indices<T>(source: &[T], count: usize) -> Result<Input<Vec<usize>>>
+    where T: fmt::Display,
 {
     if count > source.len() {
-        panic!(format!("Can't satisfy count in select_many! Need {}, but only have {}.", count, source.len()));
+        return Err(RftgError::InsufficientCards { needed: count, available: source.len() });
     }
 
     for (i, option) in source.iter().enumerate() {
@@ -115,17 +167,28 @@ pub fn select_many<'a, T>(source: &'a Vec<T>, count: usize) -> Vec<T>
 
     while chosen.len() < count {
         let left = count - chosen.len();
-        write!(&mut io::stdout(), "({} left) ", left).ok().expect("Could not write to stdout!");
-        io::stdout().flush().ok().expect("Could not flush stdout!");
+        write!(&mut io::stdout(), "({} left) ", left)?;
+        io::stdout().flush()?;
 
-        let to_keep = get_num(&available_numbers) - 1;
+        let to_keep = match get_num(&available_numbers)? {
+            Input::Answer(to_keep) => to_keep,
+            Input::Undo => return Ok(Input::Undo),
+            Input::Command(command) => return Ok(Input::Command(command)),
+        };
         available_numbers.retain(|n| { *n != to_keep });
-        chosen.push(to_keep);
+        chosen.push(to_keep - 1);
     }
 
-    source.iter()
-        .enumerate()
-        .filter(|&(index, _)| { chosen.contains(&index) })
-        .map(|(_, item)| { item.clone() })
-        .collect()
+    chosen.sort();
+    Ok(Input::Answer(chosen))
+}
+
+/// Find the variant whose name matches, ignoring case, spaces, dashes and
+/// underscores, so "rare elements" finds `Good::RareElements`.
+pub fn parse_variant<T: Variants + fmt::Debug>(name: &str) -> Option<T> {
+    let normalize = |s: &str| -> String {
+        s.chars().filter(|c| { c.is_alphanumeric() }).flat_map(|c| { c.to_lowercase() }).collect()
+    };
+    let wanted = normalize(name);
+    T::variants().into_iter().find(|variant| { normalize(&format!("{:?}", variant)) == wanted })
 }